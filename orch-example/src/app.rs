@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+use omc_galaxy::messages::{CommandFailure, CommandReply, GameToUi, UiToGame};
+use omc_galaxy::{ExportFormat, GalaxySnapshot, MetricsFormat};
+use ratatui::crossterm::event::KeyCode;
+use ratatui::widgets::TableState;
+
+//Older lines are dropped from the event log
+const LOG_CAPACITY: usize = 200;
+//Written in the working directory when the topology export arrives
+const EXPORT_FILE: &str = "galaxy.dot";
+//Written in the working directory when the metrics export arrives
+const METRICS_FILE: &str = "galaxy.metrics.csv";
+//Written by the game, load it with `cargo run -- --checkpoint galaxy.checkpoint.json`
+const CHECKPOINT_FILE: &str = "galaxy.checkpoint.json";
+
+/// State of the terminal dashboard
+pub struct App {
+    pub snapshot: Option<GalaxySnapshot>,
+    //Last bag received for each explorer, already formatted
+    pub bags: BTreeMap<u32, String>,
+    pub log: VecDeque<String>,
+    pub planet_table: TableState,
+    pub should_quit: bool,
+    //The game stopped by itself, the dashboard stays open to show the scoreboard
+    pub game_over: bool,
+    sender_ui_game: Sender<UiToGame>,
+    receiver_ui_game: Receiver<GameToUi>,
+}
+
+impl App {
+    pub fn new(sender_ui_game: Sender<UiToGame>, receiver_ui_game: Receiver<GameToUi>) -> Self {
+        Self {
+            snapshot: None,
+            bags: BTreeMap::new(),
+            log: VecDeque::new(),
+            planet_table: TableState::default().with_selected(Some(0)),
+            should_quit: false,
+            game_over: false,
+            sender_ui_game,
+            receiver_ui_game,
+        }
+    }
+
+    pub fn push_log(&mut self, line: String) {
+        if self.log.len() == LOG_CAPACITY {
+            self.log.pop_front();
+        }
+        self.log.push_back(line);
+    }
+
+    /// Handles every message sent by the game since the last call
+    pub fn poll_game(&mut self) -> Result<(), String> {
+        loop {
+            match self.receiver_ui_game.try_recv() {
+                Ok(msg) => self.handle_game_message(msg),
+                Err(TryRecvError::Empty) => return Ok(()),
+                Err(TryRecvError::Disconnected) => return Err("The game closed the UI channel".to_string()),
+            }
+        }
+    }
+
+    fn handle_game_message(&mut self, msg: GameToUi) {
+        match msg {
+            GameToUi::GameStatusUpdate { snapshot } => {
+                // Same version, nothing to redraw
+                if self.snapshot.as_ref().map(|s| s.version) == Some(snapshot.version) {
+                    return;
+                }
+                self.log_status_changes(&snapshot);
+                self.snapshot = Some(snapshot);
+            }
+            GameToUi::CommandResult(Ok(CommandReply::BagContent { explorer_id, bag })) => {
+                let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+                for resource in bag {
+                    *counts.entry(format!("{:?}", resource)).or_default() += 1;
+                }
+                let formatted = counts
+                    .iter()
+                    .map(|(resource, count)| format!("{resource} x{count}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.push_log(format!("Bag of explorer {explorer_id} received"));
+                self.bags.insert(explorer_id, formatted);
+            }
+            GameToUi::CommandResult(Ok(CommandReply::TopologyExported { content, .. })) => {
+                match std::fs::write(EXPORT_FILE, content) {
+                    Ok(()) => self.push_log(format!("Topology written to {EXPORT_FILE}")),
+                    Err(e) => self.push_log(format!("Unable to write {EXPORT_FILE}: {e}")),
+                }
+            }
+            GameToUi::CommandResult(Ok(CommandReply::MetricsExported { content, .. })) => {
+                match std::fs::write(METRICS_FILE, content) {
+                    Ok(()) => self.push_log(format!("Metrics written to {METRICS_FILE}")),
+                    Err(e) => self.push_log(format!("Unable to write {METRICS_FILE}: {e}")),
+                }
+            }
+            GameToUi::CommandResult(Ok(reply)) => {
+                self.push_log(format!("OK: {:?}", reply));
+            }
+            GameToUi::CommandResult(Err(CommandFailure { command, reason, .. })) => {
+                self.push_log(format!("FAILED: {:?}: {}", command, reason));
+            }
+            GameToUi::PlanetAdded { planet_id, planet_type, neighbors } => {
+                self.push_log(format!("Planet {planet_id} ({planet_type}) joined, linked to {neighbors:?}"));
+            }
+            GameToUi::PlanetRemoved { planet_id } => {
+                self.push_log(format!("Planet {planet_id} left the galaxy"));
+            }
+            GameToUi::PlanetUnresponsive { planet_id, killed } => {
+                let action = if killed { ", killed and isolated" } else { "" };
+                self.push_log(format!("Planet {planet_id} is not answering{action}"));
+            }
+            GameToUi::PlanetCrashed { planet_id, reason } => {
+                self.push_log(format!("Planet {planet_id} crashed: {reason}"));
+            }
+            GameToUi::GameOver { scoreboard } => {
+                self.game_over = true;
+                self.push_log(format!("[tick {}] Game over: {:?}", scoreboard.tick, scoreboard.reason));
+                for (id, planet) in &scoreboard.planets {
+                    self.push_log(format!("Planet {id}: {:?}, survived {} ticks", planet.status, planet.survived_ticks));
+                }
+                for (id, explorer) in &scoreboard.explorers {
+                    self.push_log(format!(
+                        "Explorer {id}: {} resources, score {}",
+                        explorer.resources.len(),
+                        explorer.score
+                    ));
+                }
+                if let Some(winner) = scoreboard.winner() {
+                    self.push_log(format!("Explorer {winner} wins"));
+                }
+            }
+        }
+    }
+
+    /// Writes in the log every planet or explorer whose status changed
+    fn log_status_changes(&mut self, snapshot: &GalaxySnapshot) {
+        let Some(previous) = self.snapshot.as_ref() else {
+            self.push_log(format!("Galaxy loaded: {} planets", snapshot.planets.len()));
+            return;
+        };
+        let mut lines = Vec::new();
+        for (id, planet) in &snapshot.planets {
+            match previous.planets.get(id) {
+                Some(old) if old.status == planet.status => {}
+                Some(old) => lines.push(format!(
+                    "[tick {}] Planet {id}: {:?} -> {:?}",
+                    snapshot.tick, old.status, planet.status
+                )),
+                None => lines.push(format!("[tick {}] Planet {id} added", snapshot.tick)),
+            }
+        }
+        for (id, explorer) in &snapshot.explorers {
+            match previous.explorers.get(id) {
+                Some(old) if old.status == explorer.status && old.planet_id == explorer.planet_id => {}
+                _ => lines.push(format!(
+                    "[tick {}] Explorer {id}: {:?} on planet {}",
+                    snapshot.tick, explorer.status, explorer.planet_id
+                )),
+            }
+        }
+        for line in lines {
+            self.push_log(line);
+        }
+    }
+
+    /// Id of the planet highlighted in the planets table
+    pub fn selected_planet(&self) -> Option<u32> {
+        let snapshot = self.snapshot.as_ref()?;
+        // The selection can go past the last row, the table clamps it only while drawing
+        let idx = self
+            .planet_table
+            .selected()?
+            .min(snapshot.planets.len().saturating_sub(1));
+        snapshot.planets.keys().nth(idx).copied()
+    }
+
+    fn send(&mut self, command: UiToGame) {
+        if self.sender_ui_game.send(command).is_err() {
+            self.push_log("Unable to send command to orch".to_string());
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char(' ') => self.send(UiToGame::StartGame),
+            KeyCode::Char('p') => self.send(UiToGame::StopGame),
+            KeyCode::Char('n') => self.send(UiToGame::StepGame),
+            KeyCode::Char('r') => self.send(UiToGame::ResetGame),
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.send(UiToGame::EndGame);
+                self.should_quit = true;
+            }
+            KeyCode::Char('a') => {
+                if let Some(planet_id) = self.selected_planet() {
+                    self.send(UiToGame::SendAsteroid { planet_id });
+                }
+            }
+            KeyCode::Char('s') => {
+                if let Some(planet_id) = self.selected_planet() {
+                    self.send(UiToGame::SendSunray { planet_id });
+                }
+            }
+            KeyCode::Char('b') => {
+                let explorer_ids: Vec<u32> = self
+                    .snapshot
+                    .as_ref()
+                    .map(|s| s.explorers.keys().copied().collect())
+                    .unwrap_or_default();
+                for explorer_id in explorer_ids {
+                    self.send(UiToGame::BagDump { explorer_id });
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(planet_id) = self.selected_planet() {
+                    self.send(UiToGame::RemovePlanet { planet_id });
+                }
+            }
+            KeyCode::Char('g') => self.send(UiToGame::ExportTopology {
+                format: ExportFormat::Dot,
+            }),
+            KeyCode::Char('m') => self.send(UiToGame::ExportMetrics {
+                format: MetricsFormat::Csv,
+            }),
+            KeyCode::Char('c') => self.send(UiToGame::SaveCheckpoint {
+                path: CHECKPOINT_FILE.to_string(),
+            }),
+            // Only a replay can move through the ticks, the live game ignores it
+            KeyCode::Char(',') | KeyCode::Char('.') => {
+                let current = self.snapshot.as_ref().map(|s| s.tick).unwrap_or(0);
+                let tick = if key == KeyCode::Char(',') {
+                    current.saturating_sub(10)
+                } else {
+                    current + 10
+                };
+                self.send(UiToGame::SeekTick { tick });
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.planet_table.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.planet_table.select_previous(),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use omc_galaxy::{GalaxyGenerator, GraphModel, TypeMix};
+use std::env;
+
+const USAGE: &str = "usage: generate_galaxy <planets> <ring|grid|random|small-world|star> [seed] [type mix, e.g. \"4C + 3D\"]";
+
+//Prints on stdout a generated galaxy in the CSV init file format
+fn main() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 || args.len() > 4 {
+        return Err(USAGE.to_string());
+    }
+
+    let planet_count: usize = args[0].parse().map_err(|_| USAGE.to_string())?;
+    let model = GraphModel::from_name(&args[1]).ok_or(USAGE.to_string())?;
+    let seed: u64 = match args.get(2) {
+        Some(seed) => seed.parse().map_err(|_| USAGE.to_string())?,
+        None => 0,
+    };
+
+    let mut generator = GalaxyGenerator::new(planet_count, model, seed);
+    if let Some(mix) = args.get(3) {
+        generator = generator.with_mix(TypeMix::parse(mix)?);
+    }
+
+    print!("{}", generator.generate_csv()?);
+    Ok(())
+}
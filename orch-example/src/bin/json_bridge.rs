@@ -0,0 +1,26 @@
+use omc_galaxy::bridge::{Transport, run_json_bridge};
+use std::env;
+
+const USAGE: &str = "usage: json_bridge [--tcp <address> | --unix <socket path>]";
+
+//Runs the game described by INPUT_FILE and controls it with line-delimited JSON
+//over stdin/stdout (default), a TCP socket or a unix socket
+fn main() -> Result<(), String> {
+    // Load env
+    dotenv::dotenv().ok();
+
+    //Give the absolute path for the init file
+    let file_path = env::var("INPUT_FILE")
+        .expect("Imposta INPUT_FILE nel file .env o come variabile d'ambiente");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let transport = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] => Transport::Stdio,
+        ["--tcp", address] => Transport::Tcp(address.to_string()),
+        #[cfg(unix)]
+        ["--unix", path] => Transport::Unix(path.into()),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    run_json_bridge(file_path, transport).map_err(|e| e.to_string())
+}
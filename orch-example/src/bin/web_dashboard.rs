@@ -0,0 +1,257 @@
+use crossbeam_channel::{Sender, unbounded};
+use omc_galaxy::messages::json::{decode_ui_command, encode_game_message};
+use omc_galaxy::messages::{GameToUi, UiToGame};
+use omc_galaxy::run_with_ui;
+use std::env;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tungstenite::Message;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+const INDEX_PAGE: &str = include_str!("../../static/index.html");
+
+//How long a websocket client waits for a command before pushing the game updates
+const CLIENT_POLL: Duration = Duration::from_millis(50);
+//How often the server checks whether the game is over while no client connects
+const ACCEPT_POLL: Duration = Duration::from_millis(50);
+
+//Senders of every connected websocket client, a client is removed when its channel is closed
+type Clients = Arc<Mutex<Vec<Sender<String>>>>;
+
+//What every connection shares with the game
+#[derive(Clone)]
+struct Dashboard {
+    sender_ui_game: Sender<UiToGame>,
+    clients: Clients,
+    //Last status sent by the game, so that a new page does not wait for the next change
+    last_status: Arc<Mutex<Option<String>>>,
+}
+
+impl Dashboard {
+    fn new(sender_ui_game: Sender<UiToGame>) -> Self {
+        Self {
+            sender_ui_game,
+            clients: Arc::new(Mutex::new(Vec::new())),
+            last_status: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    //Sends a game message to every client
+    fn broadcast(&self, msg: &GameToUi) {
+        let line = match encode_game_message(msg) {
+            Ok(line) => line,
+            Err(e) => {
+                println!("{e}");
+                return;
+            }
+        };
+        if let GameToUi::GameStatusUpdate { .. } = msg {
+            *self.last_status.lock().unwrap() = Some(line.clone());
+        }
+        self.clients
+            .lock()
+            .unwrap()
+            .retain(|client| client.send(line.clone()).is_ok());
+    }
+}
+
+//Serves a browser dashboard on localhost: the page is served over HTTP,
+//game updates and UI commands are exchanged as JSON over a websocket
+fn main() -> Result<(), String> {
+    // Load env
+    dotenv::dotenv().ok();
+
+    //Give the absolute path for the init file
+    let file_path = env::var("INPUT_FILE")
+        .expect("Imposta INPUT_FILE nel file .env o come variabile d'ambiente");
+    let address = env::args().nth(1).unwrap_or(DEFAULT_ADDRESS.to_string());
+
+    let (sender_game_ui, receiver_ui_game) = unbounded();
+    let (sender_ui_game, receiver_game_ui) = unbounded();
+
+    let game = thread::spawn(|| run_with_ui(file_path, sender_game_ui, receiver_game_ui));
+
+    let dashboard = Dashboard::new(sender_ui_game);
+    let broadcast = dashboard.clone();
+    thread::spawn(move || {
+        for msg in receiver_ui_game {
+            broadcast.broadcast(&msg);
+        }
+    });
+
+    let listener = TcpListener::bind(&address).map_err(|e| format!("Unable to listen on {address}: {e}"))?;
+    println!("Dashboard available at http://{address}");
+
+    serve(&listener, &dashboard, || game.is_finished())?;
+
+    match game.join() {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err("Errore: Il thread di gioco è terminato inaspettatamente!".to_string()),
+    }
+}
+
+/// Accepts connections until `finished` returns true
+///
+/// The listener does not block, so the server stops even if no client ever connects again
+fn serve(listener: &TcpListener, dashboard: &Dashboard, finished: impl Fn() -> bool) -> Result<(), String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Unable to configure the listener: {e}"))?;
+
+    while !finished() {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL);
+                continue;
+            }
+            Err(e) => {
+                println!("Unable to accept a connection: {e}");
+                continue;
+            }
+        };
+        //Connections are served by their own thread, blocking
+        if let Err(e) = stream.set_nonblocking(false) {
+            println!("Unable to configure the connection: {e}");
+            continue;
+        }
+        let dashboard = dashboard.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, dashboard) {
+                println!("{e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Serves the page or upgrades the connection to a websocket
+fn handle_connection(stream: TcpStream, dashboard: Dashboard) -> Result<(), String> {
+    //The request is only peeked, the websocket handshake needs to read it again
+    let mut head = [0u8; 4096];
+    let read = stream.peek(&mut head).map_err(|e| format!("Unable to read the request: {e}"))?;
+    let head = String::from_utf8_lossy(&head[..read]).to_ascii_lowercase();
+
+    if head.contains("upgrade: websocket") {
+        serve_websocket(stream, dashboard)
+    } else {
+        serve_http(stream, &head)
+    }
+}
+
+fn serve_http(mut stream: TcpStream, head: &str) -> Result<(), String> {
+    //Consume the request before answering
+    let mut request = [0u8; 4096];
+    let _ = stream.read(&mut request);
+
+    let path = head.split_whitespace().nth(1).unwrap_or("/");
+    let response = match path {
+        "/" | "/index.html" => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            INDEX_PAGE.len(),
+            INDEX_PAGE
+        ),
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+    stream
+        .write_all(response.as_bytes())
+        .map_err(|e| format!("Unable to answer the request: {e}"))
+}
+
+fn serve_websocket(stream: TcpStream, dashboard: Dashboard) -> Result<(), String> {
+    let mut socket = tungstenite::accept(stream).map_err(|e| format!("Websocket handshake failed: {e}"))?;
+    socket
+        .get_mut()
+        .set_read_timeout(Some(CLIENT_POLL))
+        .map_err(|e| format!("Unable to configure the websocket: {e}"))?;
+
+    let (sender_client, receiver_client) = unbounded();
+    if let Some(status) = dashboard.last_status.lock().unwrap().clone() {
+        let _ = sender_client.send(status);
+    }
+    dashboard.clients.lock().unwrap().push(sender_client.clone());
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => match decode_ui_command(text.as_str()) {
+                Ok(command) => {
+                    if dashboard.sender_ui_game.send(command).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let _ = sender_client.send(error_line(&e));
+                }
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+
+        while let Ok(line) = receiver_client.try_recv() {
+            if socket.send(Message::text(line)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+//Same format used by the JSON bridge for the lines it cannot decode
+fn error_line(reason: &str) -> String {
+    serde_json::json!({ "BridgeError": reason }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use omc_galaxy::messages::json::encode_ui_command;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    //Everything runs on localhost, no game is started: the test plays the game side of the channels
+    #[test]
+    fn test_dashboard_serves_the_page_and_the_websocket_on_localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender_ui_game, receiver_game_ui) = unbounded();
+        let dashboard = Dashboard::new(sender_ui_game);
+        let status = encode_game_message(&GameToUi::PlanetRemoved { planet_id: 3 }).unwrap();
+        *dashboard.last_status.lock().unwrap() = Some(status.clone());
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let server = {
+            let dashboard = dashboard.clone();
+            let finished = Arc::clone(&finished);
+            thread::spawn(move || serve(&listener, &dashboard, || finished.load(Ordering::SeqCst)))
+        };
+
+        //Page
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(INDEX_PAGE));
+
+        //Websocket: the last status arrives first, then commands reach the game
+        let (mut socket, handshake) = tungstenite::connect(format!("ws://{address}/ws")).unwrap();
+        assert_eq!(handshake.status().as_u16(), 101);
+        assert_eq!(socket.read().unwrap().into_text().unwrap().as_str(), status);
+
+        socket
+            .send(Message::text(encode_ui_command(&UiToGame::StartGame).unwrap()))
+            .unwrap();
+        assert!(matches!(
+            receiver_game_ui.recv_timeout(Duration::from_secs(2)),
+            Ok(UiToGame::StartGame)
+        ));
+
+        //The server stops without another connection
+        finished.store(true, Ordering::SeqCst);
+        assert!(server.join().unwrap().is_ok());
+    }
+}
@@ -1,78 +1,92 @@
-use crossbeam_channel::{Receiver, Sender, unbounded};
+mod app;
+mod ui;
+
+use crossbeam_channel::unbounded;
 use omc_galaxy::settings;
-use omc_galaxy::{Game, run_with_ui};
+use omc_galaxy::{run_from_checkpoint, run_replay, run_with_ui};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
 use std::env;
-use std::io;
-use std::io::Write;
-use std::{thread, time};
-use omc_galaxy::messages::{UiToGame, GameToUi};
+use std::thread;
+use std::time::Duration;
+
+use crate::app::App;
+
+//How long the dashboard waits for a key before checking the game messages again
+const INPUT_POLL: Duration = Duration::from_millis(100);
 
 //This main let us terminate in an elegant and simple way, returning the error message
 fn main() -> Result<(), String> {
     // Load env
     dotenv::dotenv().ok();
 
+    //With --replay <journal> a finished game is played back instead of the galaxy of INPUT_FILE,
+    //with --checkpoint <file> a saved game goes on
+    let replay_path = env::args()
+        .skip_while(|arg| arg != "--replay")
+        .nth(1);
+    let checkpoint_path = env::args()
+        .skip_while(|arg| arg != "--checkpoint")
+        .nth(1);
+
     //Give the absolute path for the init file
-    let file_path = env::var("INPUT_FILE")
-        .expect("Imposta INPUT_FILE nel file .env o come variabile d'ambiente");
+    let file_path = match (&replay_path, &checkpoint_path) {
+        (Some(_), _) | (_, Some(_)) => String::new(),
+        (None, None) => env::var("INPUT_FILE")
+            .expect("Imposta INPUT_FILE nel file .env o come variabile d'ambiente"),
+    };
 
     // let sequence = "AAAAAAA".to_string();
     // settings::set_sunray_asteroid_sequence(sequence);
     settings::set_sunray_asteroid_sequence("AAAAAAASSS".to_string());
-    let sequence = settings::pop_sunray_asteroid_sequence();
-    println!("{:?}", sequence);
-
-    println!("{}", settings::get_sunray_asteroid_sequence());
 
     let (sender_game_ui, receiver_ui_game) = unbounded();
     let (sender_ui_game, receiver_game_ui) = unbounded();
 
-    // thread::spawn(|| -> Result<(), String> {
-    //     run_with_ui(file_path, sender_game_ui, receiver_game_ui)
-    // });
-
-    let handle = thread::spawn(|| run_with_ui(file_path, sender_game_ui, receiver_game_ui));
+    let handle = thread::spawn(|| match (replay_path, checkpoint_path) {
+        (Some(journal_path), _) => run_replay(journal_path, sender_game_ui, receiver_game_ui),
+        (None, Some(checkpoint_path)) => run_from_checkpoint(checkpoint_path, sender_game_ui, receiver_game_ui),
+        (None, None) => run_with_ui(file_path, sender_game_ui, receiver_game_ui),
+    });
 
-    thread::sleep(time::Duration::from_millis(1000));
+    let mut app = App::new(sender_ui_game, receiver_ui_game);
+    let mut terminal = ratatui::init();
 
-    loop {
-        if handle.is_finished() {
-            println!("Errore: Il thread di gioco è terminato inaspettatamente!");
-            break;
+    let result = loop {
+        //The last messages of a finished game (e.g. the scoreboard) are read before checking why it ended
+        let game_finished = handle.is_finished();
+        if !app.game_over
+            && let Err(e) = app.poll_game()
+        {
+            app.push_log(e);
+        }
+        if game_finished && !app.should_quit && !app.game_over {
+            break Err("Errore: Il thread di gioco è terminato inaspettatamente!".to_string());
+        }
+        if let Err(e) = terminal.draw(|frame| ui::draw(frame, &mut app)) {
+            break Err(format!("Unable to draw the dashboard: {e}"));
+        }
+        if app.should_quit {
+            break Ok(());
         }
-        println!("Enter command:");
-        io::stdout().flush().unwrap();
 
-        let mut command = String::new();
-        io::stdin()
-            .read_line(&mut command)
-            .expect("failed to readline");
-        match command.as_str().trim() {
-            "start" => {
-                sender_ui_game
-                    .send(UiToGame::StartGame)
-                    .map_err(|_| "Unable to send command to orch")?;
-            }
-            "stop" => {
-                sender_ui_game
-                    .send(UiToGame::StopGame)
-                    .map_err(|_| "Unable to send command to orch")?;
-            }
-            "reset" => {
-                sender_ui_game
-                    .send(UiToGame::ResetGame)
-                    .map_err(|_| "Unable to send command to orch")?;
-            }
-            "end" => {
-                sender_ui_game
-                    .send(UiToGame::EndGame)
-                    .map_err(|_| "Unable to send command to orch")?;
-            }
-            _ => {
-                println!("Invalid input")
+        match event::poll(INPUT_POLL) {
+            Ok(true) => {
+                if let Ok(Event::Key(key)) = event::read()
+                    && key.kind == KeyEventKind::Press
+                {
+                    app.handle_key(key.code);
+                }
             }
+            Ok(false) => {}
+            Err(e) => break Err(format!("Unable to read the terminal events: {e}")),
         }
-    }
+    };
 
-    Ok(())
+    ratatui::restore();
+
+    //The game thread returns the reason why it stopped
+    if let Ok(Err(reason)) = handle.join() {
+        println!("{reason}");
+    }
+    result
 }
@@ -0,0 +1,177 @@
+use omc_galaxy::{GalaxySnapshot, Status};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, Wrap};
+
+use crate::app::App;
+
+const KEY_HELP: &str = "space start/resume | p pause | n step | r reset | q end | ↑/↓ select planet | a asteroid | s sunray | b bags | x remove planet | g export graph | m metrics | c checkpoint | ,/. replay -10/+10 ticks";
+
+fn status_color(status: &Status) -> Color {
+    match status {
+        Status::Running => Color::Green,
+        Status::Paused => Color::Yellow,
+        Status::Unresponsive => Color::Red,
+        Status::Dead => Color::DarkGray,
+        Status::Crashed => Color::Magenta,
+    }
+}
+
+/// Draws the whole dashboard
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [main_area, log_area, help_area] = Layout::vertical([
+        Constraint::Min(10),
+        Constraint::Length(10),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [graph_area, tables_area] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(main_area);
+    let [planets_area, explorers_area] =
+        Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(tables_area);
+
+    let title = match &app.snapshot {
+        Some(snapshot) => format!(
+            " Galaxy - tick {} - {} components, diameter {} ",
+            snapshot.tick,
+            snapshot.analysis.components.len(),
+            snapshot.analysis.diameter
+        ),
+        None => " Galaxy - waiting for the first update ".to_string(),
+    };
+
+    match app.snapshot.clone() {
+        Some(snapshot) => {
+            draw_graph(frame, graph_area, &snapshot, title);
+            draw_planets(frame, planets_area, &snapshot, app);
+            draw_explorers(frame, explorers_area, &snapshot, app);
+        }
+        None => {
+            frame.render_widget(
+                Paragraph::new("Press space to start the game").block(Block::bordered().title(title)),
+                graph_area,
+            );
+        }
+    }
+    draw_log(frame, log_area, app);
+    frame.render_widget(
+        Paragraph::new(KEY_HELP).style(Style::default().add_modifier(Modifier::REVERSED)),
+        help_area,
+    );
+}
+
+/// Adjacency list of the galaxy, every planet colored by its status
+fn draw_graph(frame: &mut Frame, area: Rect, snapshot: &GalaxySnapshot, title: String) {
+    let lines: Vec<Line> = snapshot
+        .topology
+        .planet_ids
+        .iter()
+        .map(|planet_id| {
+            let color = snapshot
+                .planets
+                .get(planet_id)
+                .map(|p| status_color(&p.status))
+                .unwrap_or(Color::Gray);
+            //Critical planets split the galaxy when destroyed
+            let marker = if snapshot.analysis.is_articulation_point(*planet_id) { "◆" } else { "●" };
+            let mut spans = vec![Span::styled(format!("{marker} {planet_id:>3}"), Style::default().fg(color)), Span::raw(" ── ")];
+            for neighbor in snapshot.topology.neighbors(*planet_id) {
+                let neighbor_color = snapshot
+                    .planets
+                    .get(&neighbor)
+                    .map(|p| status_color(&p.status))
+                    .unwrap_or(Color::Gray);
+                spans.push(Span::styled(format!("{neighbor} "), Style::default().fg(neighbor_color)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn draw_planets(frame: &mut Frame, area: Rect, snapshot: &GalaxySnapshot, app: &mut App) {
+    let rows: Vec<Row> = snapshot
+        .planets
+        .iter()
+        .map(|(id, planet)| {
+            let explorers = snapshot
+                .explorers
+                .values()
+                .filter(|e| e.planet_id == *id && e.status != Status::Dead)
+                .count();
+            Row::new(vec![
+                id.to_string(),
+                planet
+                    .planet_type
+                    .map(|t| match t.class() {
+                        Some(class) => format!("{t} ({class:?})"),
+                        None => t.to_string(),
+                    })
+                    .unwrap_or_else(|| "-".to_string()),
+                format!("{:?}", planet.status),
+                explorers.to_string(),
+            ])
+            .style(Style::default().fg(status_color(&planet.status)))
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(5),
+            Constraint::Min(22),
+            Constraint::Length(12),
+            Constraint::Length(9),
+        ],
+    )
+    .header(Row::new(vec!["Id", "Type", "Status", "Explorers"]).style(Style::default().add_modifier(Modifier::BOLD)))
+    .block(Block::bordered().title(" Planets "))
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, area, &mut app.planet_table);
+}
+
+fn draw_explorers(frame: &mut Frame, area: Rect, snapshot: &GalaxySnapshot, app: &App) {
+    let rows: Vec<Row> = snapshot
+        .explorers
+        .iter()
+        .map(|(id, explorer)| {
+            Row::new(vec![
+                id.to_string(),
+                explorer.planet_id.to_string(),
+                format!("{:?}", explorer.status),
+                app.bags.get(id).cloned().unwrap_or_else(|| "press b".to_string()),
+            ])
+            .style(Style::default().fg(status_color(&explorer.status)))
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(5),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Min(20),
+        ],
+    )
+    .header(Row::new(vec!["Id", "Planet", "Status", "Bag"]).style(Style::default().add_modifier(Modifier::BOLD)))
+    .block(Block::bordered().title(" Explorers "));
+    frame.render_widget(table, area);
+}
+
+/// Last lines of the event log, the newest at the bottom
+fn draw_log(frame: &mut Frame, area: Rect, app: &App) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = app
+        .log
+        .iter()
+        .skip(app.log.len().saturating_sub(visible))
+        .map(|line| Line::raw(line.as_str()))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Events ")), area);
+}
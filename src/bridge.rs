@@ -0,0 +1,179 @@
+//! Bridge between the game and an external front end speaking line-delimited JSON
+//!
+//! The bridge runs `run_with_ui` in its own thread and forwards:
+//! - every line read from the transport, decoded as a `UiToGame`, to the game
+//! - every `GameToUi` produced by the game, encoded on a single line, to the transport
+//!
+//! Lines that cannot be decoded are answered with `{"BridgeError":"<reason>"}`,
+//! the game is not affected by them. When the input is closed the game is ended,
+//! when the game ends by itself (e.g. `GameOver`) the bridge stops reading and returns.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::thread;
+
+use common_game::logging::Channel;
+use crossbeam_channel::{Receiver, Sender, select, unbounded};
+
+use crate::components::game_loop::run_with_ui;
+use crate::components::orchestrator::panic_message;
+use crate::messages::json::{decode_ui_command, encode_game_message};
+use crate::messages::UiToGame;
+use crate::utils::errors::OrchestratorError;
+use crate::utils::logging::log_event;
+
+/// Where the JSON messages are exchanged
+#[derive(Debug, Clone)]
+pub enum Transport {
+    /// Commands from stdin, game messages to stdout
+    Stdio,
+    /// Waits for a single client on the given address, e.g. "127.0.0.1:7878"
+    Tcp(String),
+    /// Waits for a single client on the given socket path
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Runs the game described by `file_path` and exposes it over `transport`
+///
+/// Returns when the game ends, with the reason why it ended
+pub fn run_json_bridge(file_path: String, transport: Transport) -> Result<(), OrchestratorError> {
+    match transport {
+        Transport::Stdio => serve(file_path, BufReader::new(std::io::stdin()), std::io::stdout()),
+        Transport::Tcp(address) => {
+            let listener = TcpListener::bind(&address)
+                .map_err(|e| transport_error(format!("Unable to listen on {address}: {e}")))?;
+            let (stream, peer) = listener
+                .accept()
+                .map_err(|e| transport_error(format!("Unable to accept a client on {address}: {e}")))?;
+            log_event!(Channel::Info, 0, None, "BridgeConnected", "client connected from {peer}");
+            let reader = stream
+                .try_clone()
+                .map_err(|e| transport_error(format!("Unable to clone the client stream: {e}")))?;
+            serve(file_path, BufReader::new(reader), stream)
+        }
+        #[cfg(unix)]
+        Transport::Unix(path) => {
+            //A socket file left by a previous run would make bind fail
+            let _ = std::fs::remove_file(&path);
+            let listener = UnixListener::bind(&path)
+                .map_err(|e| transport_error(format!("Unable to listen on {}: {e}", path.display())))?;
+            let (stream, _) = listener
+                .accept()
+                .map_err(|e| transport_error(format!("Unable to accept a client on {}: {e}", path.display())))?;
+            let reader = stream
+                .try_clone()
+                .map_err(|e| transport_error(format!("Unable to clone the client stream: {e}")))?;
+            let result = serve(file_path, BufReader::new(reader), stream);
+            let _ = std::fs::remove_file(&path);
+            result
+        }
+    }
+}
+
+/// Exchanges the messages between the game and any reader/writer pair
+pub fn serve<R, W>(file_path: String, reader: R, mut writer: W) -> Result<(), OrchestratorError>
+where
+    R: BufRead + Send + 'static,
+    W: Write + Send + 'static,
+{
+    let (sender_game_ui, receiver_ui_game) = unbounded();
+    let (sender_ui_game, receiver_game_ui) = unbounded();
+
+    //Every line written to the client passes from here, so game messages and bridge errors never interleave
+    let (sender_out, receiver_out) = unbounded::<String>();
+
+    let game = thread::spawn(move || run_with_ui(file_path, sender_game_ui, receiver_game_ui));
+
+    //Closed by the forwarder when the game thread has ended and dropped its sender
+    let (sender_game_finished, receiver_game_finished) = unbounded::<()>();
+    let sender_game_out = sender_out.clone();
+    let forwarder = thread::spawn(move || {
+        let _game_finished = sender_game_finished;
+        for msg in receiver_ui_game {
+            let line = match encode_game_message(&msg) {
+                Ok(line) => line,
+                Err(e) => error_line(&e),
+            };
+            if sender_game_out.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let writer_thread = thread::spawn(move || {
+        for line in receiver_out {
+            if writeln!(writer, "{line}").and_then(|_| writer.flush()).is_err() {
+                break;
+            }
+        }
+    });
+
+    //Not joined: it stays blocked on the input of a client that never closes it
+    let (sender_lines, receiver_lines) = unbounded();
+    thread::spawn(move || {
+        for line in reader.lines() {
+            if sender_lines.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    read_commands(&receiver_lines, &receiver_game_finished, &sender_ui_game, &sender_out);
+
+    //The input is closed or the game is over, the game has to end
+    let _ = sender_ui_game.send(UiToGame::EndGame);
+    drop(sender_ui_game);
+    let game_result = game.join().map_err(|payload| OrchestratorError::GameCrashed {
+        reason: panic_message(payload),
+    })?;
+
+    let _ = forwarder.join();
+    drop(sender_out);
+    let _ = writer_thread.join();
+    game_result
+}
+
+fn read_commands(
+    receiver_lines: &Receiver<std::io::Result<String>>,
+    receiver_game_finished: &Receiver<()>,
+    sender_ui_game: &Sender<UiToGame>,
+    sender_out: &Sender<String>,
+) {
+    loop {
+        //Nothing is sent on receiver_game_finished, it only gets closed
+        let line = select! {
+            recv(receiver_lines) -> line => line.ok().and_then(Result::ok),
+            recv(receiver_game_finished) -> _ => None,
+        };
+        let Some(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match decode_ui_command(&line) {
+            Ok(command) => {
+                let is_end = matches!(command, UiToGame::EndGame);
+                if sender_ui_game.send(command).is_err() || is_end {
+                    break;
+                }
+            }
+            Err(e) => {
+                let _ = sender_out.send(error_line(&e));
+            }
+        }
+    }
+}
+
+fn transport_error(reason: String) -> OrchestratorError {
+    OrchestratorError::Transport { reason }
+}
+
+fn error_line(reason: &str) -> String {
+    serde_json::json!({ "BridgeError": reason }).to_string()
+}
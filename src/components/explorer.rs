@@ -1,11 +1,24 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
 use common_game::components::resource::{BasicResource, BasicResourceType, ComplexResource, ComplexResourceRequest, ComplexResourceType, GenericResource, ResourceType};
 use crossbeam_channel::{Receiver, Sender, select};
 
 use common_game::protocols::orchestrator_explorer::{ExplorerToOrchestrator, OrchestratorToExplorer};
 use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+use common_game::logging::Channel;
 use common_game::utils::ID;
 
+use crate::utils::errors::Actor;
+use crate::utils::logging::{game_tick, log_event};
+use crate::utils::topology_analysis::{SharedTopologyAnalysis, TopologyAnalysis};
+
+// fake orchestrator and planet used to test the explorer in isolation
+#[cfg(test)]
+pub(crate) mod harness;
+
+// how long the explorer waits for a message before going on with its own logic
+const IDLE_WAIT: Duration = Duration::from_millis(10);
+
 // the type that is returned to the orchestrator when he asks for the explorer's bag
 pub type BagType = Vec<ResourceType>;
 
@@ -170,6 +183,7 @@ struct PlanetInfo {
 // TODO memorizzare topologia, celle libere (utili per AI se non ci sono 2 explorer), risorse generate/combinate per ogni pianeta
 
 // these are the states of the explorer state machine
+#[derive(Debug, PartialEq)]
 pub enum ExplorerState {
     Idle,
     WaitingToStartExplorerAI,
@@ -213,10 +227,10 @@ pub fn start_explorer_ai(explorer: &mut Explorer){
     match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::StartExplorerAIResult { explorer_id: explorer.explorer_id }) {
         Ok(_) => {
             explorer.state = ExplorerState::Idle;
-            println!("[EXPLORER DEBUG] Start explorer AI result sent correctly.")
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "StartExplorerAIResult", "sent");
         },
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending start explorer AI result: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "StartExplorerAIResult", "unable to send: {:?}", err);
             // TODO killare il thread / panicare o non gestire l'errore?
         }
     }
@@ -230,10 +244,10 @@ pub fn reset_explorer_ai(explorer: &mut Explorer){
             // TODO reset anche dell'inventario?
             explorer.topology_info.clear();
             explorer.state = ExplorerState::Idle;
-            println!("[EXPLORER DEBUG] Reset explorer AI result sent correctly.")
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "ResetExplorerAIResult", "sent");
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending reset explorer AI result: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "ResetExplorerAIResult", "unable to send: {:?}", err);
         }
     }
 }
@@ -244,10 +258,10 @@ pub fn stop_explorer_ai(explorer: &mut Explorer){
     match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::StopExplorerAIResult { explorer_id: explorer.explorer_id }) {
         Ok(_) => {
             explorer.state = ExplorerState::WaitingToStartExplorerAI;
-            println!("[EXPLORER DEBUG] Stop explorer AI result sent correctly.")
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "StopExplorerAIResult", "sent");
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending stop explorer AI result: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "StopExplorerAIResult", "unable to send: {:?}", err);
         }
     }
 }
@@ -257,10 +271,10 @@ pub fn kill_explorer(explorer: &mut Explorer){
     match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::KillExplorerResult { explorer_id: explorer.explorer_id }) {
         Ok(_) => {
             explorer.state = ExplorerState::Killed;
-            println!("[EXPLORER DEBUG] Kill explorer result sent correctly.")
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "KillExplorerResult", "sent");
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending kill explorer result: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "KillExplorerResult", "unable to send: {:?}", err);
         }
     }
 }
@@ -272,10 +286,10 @@ pub fn move_to_planet(explorer: &mut Explorer, sender_to_new_planet: Option<Send
         Some(sender) => {
             explorer.planet_channels.1 = sender;
             explorer.planet_id = explorer.next_planet_id.clone();
-            println!("[EXPLORER DEBUG] Sender channel set correctly");
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "MoveToPlanet", "moved to planet {}", explorer.planet_id);
         }
         None => {
-            println!("[EXPLORER DEBUG] Sender channel is None.");
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "MoveToPlanet", "no sender to the new planet");
         }
     }
 }
@@ -285,10 +299,10 @@ pub fn current_planet_request(explorer: &mut Explorer){
     match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::CurrentPlanetResult { explorer_id: explorer.explorer_id, planet_id: explorer.planet_id }) {
         Ok(_) => {
             explorer.state = ExplorerState::Idle;
-            println!("[EXPLORER DEBUG] Current planet result sent correctly.")
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CurrentPlanetResult", "sent");
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending current planet result: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CurrentPlanetResult", "unable to send: {:?}", err);
         }
     }
 }
@@ -308,10 +322,10 @@ pub fn supperted_resource_request(explorer: &mut Explorer){
     } else {
         match explorer.planet_channels.1.send(ExplorerToPlanet::SupportedResourceRequest { explorer_id: explorer.explorer_id }) {
             Ok(_) => {
-                println!("[EXPLORER DEBUG] Supported resource request sent correctly from explorer.");
+                log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedResourceRequest", "sent to planet {}", explorer.planet_id);
             }
             Err(err) => {
-                println!("[EXPLORER DEBUG] Error sending supported resource request from explorer: {:?}", err);
+                log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedResourceRequest", "unable to send to planet {}: {:?}", explorer.planet_id, err);
             }
         }
         match explorer.planet_channels.0.recv() {
@@ -321,22 +335,22 @@ pub fn supperted_resource_request(explorer: &mut Explorer){
                         supported_resources = resource_list;
                     }
                     _ => {
-                        println!("[EXPLORER DEBUG] Unexpected response to SupportedResourceRequest.");
+                        log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedResourceRequest", "unexpected response from planet {}", explorer.planet_id);
                     }
                 }
             }
             Err(err) => {
-                println!("[EXPLORER DEBUG] Error receiving supported resources from planet: {:?}", err);
+                log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedResourceResponse", "unable to receive from planet {}: {:?}", explorer.planet_id, err);
             }
         }
     }
     match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::SupportedResourceResult { explorer_id: explorer.explorer_id ,supported_resources }) {
         Ok(_) => {
             explorer.state = ExplorerState::Idle;
-            println!("[EXPLORER DEBUG] Supported resource result sent correctly from explorer to orchestrator.");
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedResourceResult", "sent");
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending supported resource result from explorer to orchestrator: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedResourceResult", "unable to send: {:?}", err);
         }
     }
 }
@@ -356,10 +370,10 @@ pub fn supported_combination_request(explorer: &mut Explorer){
     } else {
         match explorer.planet_channels.1.send(ExplorerToPlanet::SupportedCombinationRequest { explorer_id: explorer.explorer_id }) {
             Ok(_) => {
-                println!("[EXPLORER DEBUG] Supported combination request sent correctly from explorer.");
+                log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedCombinationRequest", "sent to planet {}", explorer.planet_id);
             }
             Err(err) => {
-                println!("[EXPLORER DEBUG] Error sending supported combination request from explorer: {:?}", err);
+                log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedCombinationRequest", "unable to send to planet {}: {:?}", explorer.planet_id, err);
             }
         }
         match explorer.planet_channels.0.recv() {
@@ -369,22 +383,22 @@ pub fn supported_combination_request(explorer: &mut Explorer){
                         supported_combinations = combination_list;
                     }
                     _ => {
-                        println!("[EXPLORER DEBUG] Unexpected response to SupportedCombinationRequest.");
+                        log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedCombinationRequest", "unexpected response from planet {}", explorer.planet_id);
                     }
                 }
             }
             Err(err) => {
-                println!("[EXPLORER DEBUG] Error receiving supported combinations from planet: {:?}", err);
+                log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedCombinationResponse", "unable to receive from planet {}: {:?}", explorer.planet_id, err);
             }
         }
     }
     match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::SupportedCombinationResult { explorer_id: explorer.explorer_id, combination_list: supported_combinations}) {
         Ok(_) => {
             explorer.state = ExplorerState::Idle;
-            println!("[EXPLORER DEBUG] Supported combination result sent correctly from explorer to orchestrator.");
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedCombinationResult", "sent");
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending supported combination result from explorer to orchestrator: {:?}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "SupportedCombinationResult", "unable to send: {:?}", err);
         }
     }
 }
@@ -394,10 +408,10 @@ pub fn supported_combination_request(explorer: &mut Explorer){
 pub fn generate_resource_request(explorer: &mut Explorer, to_generate: BasicResourceType){
     match explorer.planet_channels.1.send(ExplorerToPlanet::GenerateResourceRequest {explorer_id: explorer.explorer_id, resource: to_generate}) {
         Ok(_) => {
-            println!("[EXPLORER DEBUG] Generate resource request correctly");
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "GenerateResourceRequest", "sent to planet {}", explorer.planet_id);
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error sending generate resource request {}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "GenerateResourceRequest", "unable to send to planet {}: {}", explorer.planet_id, err);
         }
     }
     match explorer.planet_channels.0.recv() {
@@ -406,11 +420,11 @@ pub fn generate_resource_request(explorer: &mut Explorer, to_generate: BasicReso
                 PlanetToExplorer::GenerateResourceResponse{ resource } => {
                     put_basic_resource_in_the_bag(explorer, resource);
                 }
-                _ => println!("[EXPLORER DEBUG] Unexpected response to generate resource request"),
+                _ => log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "GenerateResourceRequest", "unexpected response from planet {}", explorer.planet_id),
             }
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error receiving generate resource response {}", err);
+            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "GenerateResourceResponse", "unable to receive from planet {}: {}", explorer.planet_id, err);
         }
     }
 }
@@ -456,10 +470,10 @@ pub fn combine_resource_request(explorer: &mut Explorer, to_generate: ComplexRes
         Ok(complex_resource_req) => {
             match explorer.planet_channels.1.send(ExplorerToPlanet::CombineResourceRequest { explorer_id: explorer.explorer_id,  msg: complex_resource_req }) {
                 Ok(_) => {
-                    println!("[EXPLORER DEBUG] Combine resource request sent correctly");
+                    log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CombineResourceRequest", "{:?} requested to planet {}", to_generate, explorer.planet_id);
                 }
                 Err(err) => {
-                    println!("[EXPLORER DEBUG] Error sending combine resource request {}", err);
+                    log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CombineResourceRequest", "unable to send to planet {}: {}", explorer.planet_id, err);
                 }
             }
             match explorer.planet_channels.0.recv() {
@@ -480,20 +494,20 @@ pub fn combine_resource_request(explorer: &mut Explorer, to_generate: ComplexRes
                                     explorer.bag.insert(generic_resource);
                                 }
                                 Err(err) => {
-                                    println!("[EXPLORER DEBUG] Error receiving CombineResourceResponse: {:?}", err)
+                                    log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CombineResourceResponse", "the planet could not combine: {:?}", err);
                                 }
                             }
                         }
-                        _ => println!("[EXPLORER DEBUG] Unexpected response to combine resource request"),
+                        _ => log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CombineResourceRequest", "unexpected response from planet {}", explorer.planet_id),
                     }
                 }
                 Err(err) => {
-                    println!("[EXPLORER DEBUG] Error receiving combine resource response {}", err);
+                    log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CombineResourceResponse", "unable to receive from planet {}: {}", explorer.planet_id, err);
                 }
             }
         }
         Err(err) => {
-            println!("[EXPLORER DEBUG] Error generating complex resource request {}", err);
+            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "CombineResourceRequest", "{:?} not requested: {}", to_generate, err);
         }
     }
 }
@@ -525,6 +539,21 @@ pub fn neighbours_response(explorer: &mut Explorer, neighbors: Vec<ID>) {
             }
         );
     }
+    // the orchestrator analysis already knows the links of the neighbours, there is no need to visit them to find out
+    let known_links: Vec<(ID, HashSet<ID>)> = explorer
+        .read_topology_analysis(|analysis| {
+            neighbors
+                .iter()
+                .filter_map(|neighbour| analysis.graph.get(neighbour).map(|links| (*neighbour, links.iter().copied().collect())))
+                .collect()
+        })
+        .unwrap_or_default();
+    for (neighbour, links) in known_links {
+        if let Some(info) = explorer.topology_info.get_mut(&neighbour) {
+            info.neighbours = Some(links);
+        }
+    }
+
     let planet_info = explorer
         .topology_info
         .get_mut(&explorer.planet_id)
@@ -549,6 +578,7 @@ pub struct Explorer {
     energy_cells: u32, // of the current planet
     buffer_orchestrator_msg: VecDeque<OrchestratorToExplorer>, // orchestrator messages that the explorer cannot respond to immediately
     buffer_planet_msg: VecDeque<PlanetToExplorer>, // planet messages that the explorer cannot respond to immediately
+    topology_analysis: Option<SharedTopologyAnalysis>, // critical planets and distances computed by the orchestrator
 }
 
 impl Explorer {
@@ -577,9 +607,23 @@ impl Explorer {
             energy_cells,
             buffer_orchestrator_msg: VecDeque::new(),
             buffer_planet_msg: VecDeque::new(),
+            topology_analysis: None,
         }
     }
 
+    // gives the explorer read access to the topology analysis kept updated by the orchestrator
+    pub fn with_topology_analysis(mut self, topology_analysis: SharedTopologyAnalysis) -> Self {
+        self.topology_analysis = Some(topology_analysis);
+        self
+    }
+
+    // reads the topology analysis kept updated by the orchestrator (critical planets, shortest paths...),
+    // None if the explorer has no access to it
+    pub fn read_topology_analysis<T>(&self, read: impl FnOnce(&TopologyAnalysis) -> T) -> Option<T> {
+        let analysis = self.topology_analysis.as_ref()?.read().ok()?;
+        Some(read(&analysis))
+    }
+
     // getter function for the id
     pub fn id(&self) -> u32 {
         self.explorer_id
@@ -592,148 +636,155 @@ impl Explorer {
         // do not match together the message is pushed into the corresponding buffer, and it will be read
         // when the explorer will be in an "Idle" state
         loop {
-            select! {
-                recv(self.orchestrator_channels.0) -> msg_orchestrator => {
-                    match msg_orchestrator {
-                        Ok(msg) => {
-                            if orch_msg_match_state(&self.state, &msg) {
-                                match msg {
-                                    OrchestratorToExplorer::StartExplorerAI => {
-                                        start_explorer_ai(self);
-                                    }
-                                    OrchestratorToExplorer::ResetExplorerAI => {
-                                        reset_explorer_ai(self);
-                                    }
-                                    OrchestratorToExplorer::StopExplorerAI => {
-                                        stop_explorer_ai(self);
-                                    }
-                                    OrchestratorToExplorer::KillExplorer => {
-                                        // TODO this action should be preemptive
-                                        kill_explorer(self);
-                                    }
-                                    OrchestratorToExplorer::MoveToPlanet{ sender_to_new_planet } => {
-                                        move_to_planet(self, sender_to_new_planet);
-                                    }
-                                    OrchestratorToExplorer::CurrentPlanetRequest => {
-                                        current_planet_request(self);
-                                    }
-                                    OrchestratorToExplorer::SupportedResourceRequest => {
-                                        // + devo fare un'attesa bloccante per ricevere le risorse supportate e poi rispondere o vado avanti? -> al momento attesa bloccante
-                                        supperted_resource_request(self);
-                                    }
-                                    OrchestratorToExplorer::SupportedCombinationRequest => {
-                                        // + devo fare un'attesa bloccante per ricevere le combinazioni supportate e poi rispondere o vado avanti?
-                                        supported_combination_request(self);
-                                    }
-                                    OrchestratorToExplorer::GenerateResourceRequest{ to_generate } => {
-                                        generate_resource_request(self, to_generate);
-                                    }
-                                    OrchestratorToExplorer::CombineResourceRequest{ to_generate } => {
-                                        // TODO verify first if the explorer has the resources to generate the combined one
-                                        combine_resource_request(self, to_generate);
-                                    }
-                                    OrchestratorToExplorer::BagContentRequest => {
-                                        // IMPORTANTE restituisce un vettore contenente i resource type e non gli item in se
-                                        match self.orchestrator_channels.1.send(ExplorerToOrchestrator::BagContentResponse {explorer_id: self.explorer_id, bag_content: self.bag.to_resource_types()}) {
-                                            Ok(_) => {
-                                                println!("[EXPLORER DEBUG] BagContent response sent correctly");
-                                            }
-                                            Err(err) => {
-                                                println!("[EXPLORER DEBUG] Error sending bag content response: {}", err);
-                                            }
+            self.step();
+        }
+    }
+
+    // a single iteration of the explorer loop: handles at most one message,
+    // or runs the idle logic if no message arrives within IDLE_WAIT
+    pub(crate) fn step(&mut self) {
+        select! {
+            recv(self.orchestrator_channels.0) -> msg_orchestrator => {
+                match msg_orchestrator {
+                    Ok(msg) => {
+                        if orch_msg_match_state(&self.state, &msg) {
+                            match msg {
+                                OrchestratorToExplorer::StartExplorerAI => {
+                                    start_explorer_ai(self);
+                                }
+                                OrchestratorToExplorer::ResetExplorerAI => {
+                                    reset_explorer_ai(self);
+                                }
+                                OrchestratorToExplorer::StopExplorerAI => {
+                                    stop_explorer_ai(self);
+                                }
+                                OrchestratorToExplorer::KillExplorer => {
+                                    // TODO this action should be preemptive
+                                    kill_explorer(self);
+                                }
+                                OrchestratorToExplorer::MoveToPlanet{ sender_to_new_planet } => {
+                                    move_to_planet(self, sender_to_new_planet);
+                                }
+                                OrchestratorToExplorer::CurrentPlanetRequest => {
+                                    current_planet_request(self);
+                                }
+                                OrchestratorToExplorer::SupportedResourceRequest => {
+                                    // + devo fare un'attesa bloccante per ricevere le risorse supportate e poi rispondere o vado avanti? -> al momento attesa bloccante
+                                    supperted_resource_request(self);
+                                }
+                                OrchestratorToExplorer::SupportedCombinationRequest => {
+                                    // + devo fare un'attesa bloccante per ricevere le combinazioni supportate e poi rispondere o vado avanti?
+                                    supported_combination_request(self);
+                                }
+                                OrchestratorToExplorer::GenerateResourceRequest{ to_generate } => {
+                                    generate_resource_request(self, to_generate);
+                                }
+                                OrchestratorToExplorer::CombineResourceRequest{ to_generate } => {
+                                    // TODO verify first if the explorer has the resources to generate the combined one
+                                    combine_resource_request(self, to_generate);
+                                }
+                                OrchestratorToExplorer::BagContentRequest => {
+                                    // IMPORTANTE restituisce un vettore contenente i resource type e non gli item in se
+                                    match self.orchestrator_channels.1.send(ExplorerToOrchestrator::BagContentResponse {explorer_id: self.explorer_id, bag_content: self.bag.to_resource_types()}) {
+                                        Ok(_) => {
+                                            log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(self.explorer_id)), "BagContentResponse", "sent");
+                                        }
+                                        Err(err) => {
+                                            log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(self.explorer_id)), "BagContentResponse", "unable to send: {}", err);
                                         }
                                     }
-                                    OrchestratorToExplorer::NeighborsResponse{ neighbors } => {
-                                        neighbours_response(self, neighbors);
-                                    }
                                 }
-                            } else {
-                                self.buffer_orchestrator_msg.push_back(msg);
+                                OrchestratorToExplorer::NeighborsResponse{ neighbors } => {
+                                    neighbours_response(self, neighbors);
+                                }
                             }
-                        }
-                        Err(err) => {
-                            println!("[EXPLORER DEBUG] Error in receiving the orchestrator message: {}", err);
+                        } else {
+                            self.buffer_orchestrator_msg.push_back(msg);
                         }
                     }
-                },
-                recv(self.planet_channels.0) -> msg_planet => {
-                    match msg_planet {
-                        Ok(msg) => {
-                            if planet_msg_match_state(&self.state, &msg) {
-                                match msg {
-                                    PlanetToExplorer::SupportedResourceResponse{ resource_list } => {
-                                        match self.topology_info.get_mut(&self.planet_id) {
-                                            Some(planet_info) => {
-                                                planet_info.basic_resources = Some(resource_list);
-                                            }
-                                            None => {
-                                                // TODO (non dovrebbe accadere) inserire il pianeta nella topologia e poi inserire la resource list
-                                            }
+                    Err(err) => {
+                        log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(self.explorer_id)), "OrchestratorMessage", "unable to receive: {}", err);
+                    }
+                }
+            },
+            recv(self.planet_channels.0) -> msg_planet => {
+                match msg_planet {
+                    Ok(msg) => {
+                        if planet_msg_match_state(&self.state, &msg) {
+                            match msg {
+                                PlanetToExplorer::SupportedResourceResponse{ resource_list } => {
+                                    match self.topology_info.get_mut(&self.planet_id) {
+                                        Some(planet_info) => {
+                                            planet_info.basic_resources = Some(resource_list);
                                         }
-                                    }
-                                    PlanetToExplorer::SupportedCombinationResponse{ combination_list } => {
-                                        match self.topology_info.get_mut(&self.planet_id) {
-                                            Some(planet_info) => {
-                                                planet_info.complex_resources = Some(combination_list);
-                                            }
-                                            None => {
-                                                // TODO (non dovrebbe accadere) inserire il pianeta nella topologia e poi inserire la combination list
-                                            }
+                                        None => {
+                                            // TODO (non dovrebbe accadere) inserire il pianeta nella topologia e poi inserire la resource list
                                         }
                                     }
-                                    PlanetToExplorer::GenerateResourceResponse{ resource } => {
-                                        if let Some(resource) = resource {
-                                            let new_resource = match resource {
-                                                BasicResource::Oxygen(oxygen) => { oxygen.to_generic() }
-                                                BasicResource::Hydrogen(hydrogen) => { hydrogen.to_generic() }
-                                                BasicResource::Carbon(carbon) => { carbon.to_generic() }
-                                                BasicResource::Silicon(silicon) => { silicon.to_generic() }
-                                            };
-                                            self.bag.insert(new_resource);
+                                }
+                                PlanetToExplorer::SupportedCombinationResponse{ combination_list } => {
+                                    match self.topology_info.get_mut(&self.planet_id) {
+                                        Some(planet_info) => {
+                                            planet_info.complex_resources = Some(combination_list);
                                         }
-                                    }
-                                    PlanetToExplorer::CombineResourceResponse{ complex_response } => {
-                                        if let Ok(complex_resource) = complex_response {
-                                            let new_resource = match complex_resource {
-                                                ComplexResource::Diamond(diamond) => { diamond.to_generic() }
-                                                ComplexResource::Water(water) => { water.to_generic() }
-                                                ComplexResource::Life(life) => { life.to_generic() }
-                                                ComplexResource::Robot(robot) => { robot.to_generic() }
-                                                ComplexResource::Dolphin(dolphin) => { dolphin.to_generic() }
-                                                ComplexResource::AIPartner(ai_partner) => { ai_partner.to_generic() }
-                                            };
-                                            self.bag.insert(new_resource);
+                                        None => {
+                                            // TODO (non dovrebbe accadere) inserire il pianeta nella topologia e poi inserire la combination list
                                         }
                                     }
-                                    PlanetToExplorer::AvailableEnergyCellResponse{ available_cells } => {
-                                        self.energy_cells = available_cells;
+                                }
+                                PlanetToExplorer::GenerateResourceResponse{ resource } => {
+                                    if let Some(resource) = resource {
+                                        let new_resource = match resource {
+                                            BasicResource::Oxygen(oxygen) => { oxygen.to_generic() }
+                                            BasicResource::Hydrogen(hydrogen) => { hydrogen.to_generic() }
+                                            BasicResource::Carbon(carbon) => { carbon.to_generic() }
+                                            BasicResource::Silicon(silicon) => { silicon.to_generic() }
+                                        };
+                                        self.bag.insert(new_resource);
                                     }
-                                    PlanetToExplorer::Stopped => {
-                                        // TODO gestire in base all'ai dell'explorer
-                                        self.state = ExplorerState::Idle;
+                                }
+                                PlanetToExplorer::CombineResourceResponse{ complex_response } => {
+                                    if let Ok(complex_resource) = complex_response {
+                                        let new_resource = match complex_resource {
+                                            ComplexResource::Diamond(diamond) => { diamond.to_generic() }
+                                            ComplexResource::Water(water) => { water.to_generic() }
+                                            ComplexResource::Life(life) => { life.to_generic() }
+                                            ComplexResource::Robot(robot) => { robot.to_generic() }
+                                            ComplexResource::Dolphin(dolphin) => { dolphin.to_generic() }
+                                            ComplexResource::AIPartner(ai_partner) => { ai_partner.to_generic() }
+                                        };
+                                        self.bag.insert(new_resource);
                                     }
                                 }
-                            } else {
-                                self.buffer_planet_msg.push_back(msg);
+                                PlanetToExplorer::AvailableEnergyCellResponse{ available_cells } => {
+                                    self.energy_cells = available_cells;
+                                }
+                                PlanetToExplorer::Stopped => {
+                                    // TODO gestire in base all'ai dell'explorer
+                                    self.state = ExplorerState::Idle;
+                                }
                             }
-                        }
-                        Err(err) => {
-                            println!("[EXPLORER DEBUG] Error in receiving the planet message: {}", err);
+                        } else {
+                            self.buffer_planet_msg.push_back(msg);
                         }
                     }
+                    Err(err) => {
+                        log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(self.explorer_id)), "PlanetMessage", "unable to receive: {}", err);
+                    }
                 }
-                default => {
-                    // TODO when sending the travelToPlanet request change the current planet id and the old planet id
-                    match self.state {
-                        ExplorerState::Idle => {
-                            // TODO gestisci i messaggi nel buffer
-                            manage_buffer_msg(self);
-                        }
-                        _ => {}
+            }
+            // waits a bit before running the default branch, otherwise the explorer thread would spin
+            default(IDLE_WAIT) => {
+                // TODO when sending the travelToPlanet request change the current planet id and the old planet id
+                match self.state {
+                    ExplorerState::Idle => {
+                        // TODO gestisci i messaggi nel buffer
+                        manage_buffer_msg(self);
                     }
-                    // TODO qui va l'AI vera e propria
-
+                    _ => {}
                 }
+                // TODO qui va l'AI vera e propria
+
             }
         }
     }
@@ -786,10 +837,10 @@ pub fn manage_buffer_msg(explorer: &mut Explorer){
                 // IMPORTANTE restituisce un vettore contenente i resource type e non gli item in se
                 match explorer.orchestrator_channels.1.send(ExplorerToOrchestrator::BagContentResponse {explorer_id: explorer.explorer_id, bag_content: explorer.bag.to_resource_types()}) {
                     Ok(_) => {
-                        println!("[EXPLORER DEBUG] BagContent response sent correctly");
+                        log_event!(Channel::Debug, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "BagContentResponse", "sent");
                     }
                     Err(err) => {
-                        println!("[EXPLORER DEBUG] Error sending bag content response: {}", err);
+                        log_event!(Channel::Warning, game_tick(), Some(Actor::Explorer(explorer.explorer_id)), "BagContentResponse", "unable to send: {}", err);
                     }
                 }
             }
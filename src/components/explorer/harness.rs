@@ -0,0 +1,137 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+use common_game::protocols::orchestrator_explorer::{ExplorerToOrchestrator, OrchestratorToExplorer};
+use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+use common_game::utils::ID;
+use crossbeam_channel::{Receiver, Sender, unbounded};
+
+use super::{BagType, Explorer, ExplorerState};
+
+// how long the harness waits for a message of the explorer before failing
+const REPLY_WAIT: Duration = Duration::from_millis(500);
+
+// an explorer connected to a fake orchestrator and to a fake planet
+//
+// the test plays the orchestrator: it sends messages with `send`, advances the explorer
+// with `step` and reads the answers with `expect_reply`.
+// The planet is scripted in advance: every request of the explorer is recorded and answered
+// with the next reply given to `script_planet_reply`, so the blocking waits of the explorer work
+pub(crate) struct ExplorerHarness {
+    pub explorer: Explorer,
+    to_explorer: Sender<OrchestratorToExplorer>,
+    from_explorer: Receiver<ExplorerToOrchestrator<BagType>>,
+    planet_replies: Arc<Mutex<VecDeque<PlanetToExplorer>>>,
+    planet_requests: Arc<Mutex<Vec<ExplorerToPlanet>>>,
+}
+
+impl ExplorerHarness {
+    // a new explorer on `planet_id`, waiting for StartExplorerAI
+    pub fn new(explorer_id: u32, planet_id: u32) -> Self {
+        let (to_explorer, from_orchestrator) = unbounded();
+        let (to_orchestrator, from_explorer) = unbounded();
+        let (to_planet, planet_receiver) = unbounded::<ExplorerToPlanet>();
+        let (planet_sender, from_planet) = unbounded();
+
+        let planet_replies = Arc::new(Mutex::new(VecDeque::new()));
+        let planet_requests = Arc::new(Mutex::new(Vec::new()));
+        {
+            let replies = Arc::clone(&planet_replies);
+            let requests = Arc::clone(&planet_requests);
+            // the fake planet lives until the explorer drops its sender
+            thread::spawn(move || {
+                for request in planet_receiver {
+                    requests.lock().unwrap().push(request);
+                    if let Some(reply) = replies.lock().unwrap().pop_front() {
+                        let _ = planet_sender.send(reply);
+                    }
+                }
+            });
+        }
+
+        Self {
+            explorer: Explorer::new(explorer_id, planet_id, (from_orchestrator, to_orchestrator), (from_planet, to_planet), 0),
+            to_explorer,
+            from_explorer,
+            planet_replies,
+            planet_requests,
+        }
+    }
+
+    // a new explorer whose AI has already been started
+    pub fn started(explorer_id: u32, planet_id: u32) -> Self {
+        let mut harness = Self::new(explorer_id, planet_id);
+        harness.send(OrchestratorToExplorer::StartExplorerAI);
+        harness.step();
+        match harness.expect_reply() {
+            ExplorerToOrchestrator::StartExplorerAIResult { .. } => harness,
+            _ => panic!("the explorer did not answer to StartExplorerAI"),
+        }
+    }
+
+    // sends a message as the orchestrator, it is handled by the next `step`
+    pub fn send(&self, msg: OrchestratorToExplorer) {
+        self.to_explorer.send(msg).expect("the explorer is gone");
+    }
+
+    // the next request of the explorer will be answered with `reply`
+    pub fn script_planet_reply(&self, reply: PlanetToExplorer) {
+        self.planet_replies.lock().unwrap().push_back(reply);
+    }
+
+    // runs one iteration of the explorer loop
+    pub fn step(&mut self) {
+        self.explorer.step();
+    }
+
+    // the next message sent by the explorer to the orchestrator
+    pub fn expect_reply(&self) -> ExplorerToOrchestrator<BagType> {
+        self.from_explorer
+            .recv_timeout(REPLY_WAIT)
+            .expect("the explorer did not send anything to the orchestrator")
+    }
+
+    pub fn assert_no_reply(&self) {
+        assert!(
+            self.from_explorer.try_recv().is_err(),
+            "the explorer sent an unexpected message to the orchestrator"
+        );
+    }
+
+    // the requests received by the fake planet so far, oldest first
+    pub fn take_planet_requests(&self) -> Vec<ExplorerToPlanet> {
+        std::mem::take(&mut *self.planet_requests.lock().unwrap())
+    }
+
+    pub fn state(&self) -> &ExplorerState {
+        &self.explorer.state
+    }
+
+    pub fn planet_id(&self) -> u32 {
+        self.explorer.planet_id
+    }
+
+    pub fn bag(&self) -> Vec<ResourceType> {
+        self.explorer.bag.to_resource_types()
+    }
+
+    pub fn buffered_messages(&self) -> (usize, usize) {
+        (self.explorer.buffer_orchestrator_msg.len(), self.explorer.buffer_planet_msg.len())
+    }
+
+    // None if the explorer knows nothing about the planet
+    pub fn known_neighbours(&self, planet_id: ID) -> Option<HashSet<ID>> {
+        self.explorer.topology_info.get(&planet_id)?.neighbours.clone()
+    }
+
+    pub fn known_basic_resources(&self, planet_id: ID) -> Option<HashSet<BasicResourceType>> {
+        self.explorer.topology_info.get(&planet_id)?.basic_resources.clone()
+    }
+
+    pub fn known_complex_resources(&self, planet_id: ID) -> Option<HashSet<ComplexResourceType>> {
+        self.explorer.topology_info.get(&planet_id)?.complex_resources.clone()
+    }
+}
@@ -4,10 +4,16 @@ use std::time::Duration;
 
 
 use crate::components::orchestrator::Orchestrator;
-use crate::debug_println;
-use crate::messages::{GameToUi, UiToGame};
+use crate::utils::logging::log_event;
+use common_game::logging::Channel;
+use crate::messages::{CommandFailure, CommandReply, GameToUi, UiToGame};
 use crate::settings;
 use crate::utils::GameState;
+use crate::utils::checkpoint::GalaxyCheckpoint;
+use crate::utils::errors::{Actor, OrchestratorError};
+use crate::utils::galaxy_file::GalaxyDefinition;
+use crate::utils::journal::{GalaxyReplay, JournalEvent};
+use crate::utils::scoring::{EndCondition, GameOverReason};
 
 
 struct GameTick {
@@ -31,6 +37,11 @@ pub struct Game {
     // UI communication
     receiver_game_ui: Receiver<UiToGame>,
     sender_game_ui: Sender<GameToUi>,
+    // Version of the last snapshot sent, used to skip unchanged frames
+    last_sent_version: Option<u64>,
+    end_conditions: Vec<EndCondition>,
+    // Set when an end condition is met, the loop stops
+    game_over: bool,
 }
 
 impl Game {
@@ -45,38 +56,60 @@ impl Game {
             orchestrator,
             receiver_game_ui,
             sender_game_ui,
+            last_sent_version: None,
+            end_conditions: settings::get_end_conditions(),
+            game_over: false,
         }
     }
 
-    fn handle_ui_command(&mut self, msg: UiToGame) -> Result<(), String> {
-        // debug_println!("The game should start for the first time");
+    /// Sends the current galaxy snapshot to the UI
+    ///
+    /// If `only_if_changed` is true the snapshot is sent only when the
+    /// orchestrator state changed since the last one sent
+    fn send_snapshot(&mut self, only_if_changed: bool) -> Result<(), OrchestratorError> {
+        if only_if_changed && self.last_sent_version == Some(self.orchestrator.state_version) {
+            return Ok(());
+        }
+        let snapshot = self.orchestrator.get_game_snapshot()?;
+        let version = snapshot.version;
+        let handle_err = self
+            .sender_game_ui
+            .send(GameToUi::GameStatusUpdate { snapshot })
+            .map_err(|_| OrchestratorError::disconnected(Actor::Ui));
+
+        // handle case error
+        match handle_err {
+            Ok(_) => self.last_sent_version = Some(version),
+            Err(e) => log_event!(Channel::Warning, self.orchestrator.current_tick, None, "GameStatusUpdate", "{e}"),
+        }
+        Ok(())
+    }
+
+    fn handle_ui_command(&mut self, msg: UiToGame) -> Result<(), OrchestratorError> {
+        self.orchestrator.record(JournalEvent::UiCommand(msg.clone()));
         match (self.state, msg) {
             (_, UiToGame::EndGame) => {
-                debug_println!("The game should end now");
+                log_event!(Channel::Info, self.orchestrator.current_tick, None, "EndGame", "the game should end now");
+                self.save_metrics();
                 self.orchestrator.send_planet_kill_to_all()?;
-                return Err("The game is terminated".to_string());
+                return Err(OrchestratorError::GameEnded);
                 // self.orchestrator.stop_all()?;
                 // self.notify_ui(GameToUi::GameEnded)?;
                 // return Ok(true); // Exit loop
             }
             (GameState::WaitingStart, UiToGame::StartGame) => {
-                debug_println!("The game should start for the first time");
+                log_event!(Channel::Info, 0, None, "StartGame", "the game starts for the first time");
                 self.game_tick = GameTick::new(Duration::from_millis(1000));
                 self.state = GameState::Running;
 
-                //Send the update to UI
-                let update = self.orchestrator.get_game_status()?;
-                let handle_err = self.sender_game_ui.send(GameToUi::GameStatusUpdate { galaxy_topology: update.0, planets_status: update.1, explorer_status: update.2 }).map_err(|_|"Unable to send messages to UI");
-
-                // handle case error
-                if let Err(e) = handle_err {
-                    debug_println!("Error sending game status update to UI: {}", e);
-                }
                 // self.notify_ui(GameToUi::GameStarted)?;
                 self.orchestrator.start_all()?;
+
+                //Send the update to UI
+                self.send_snapshot(false)?;
             }
             (GameState::Paused, UiToGame::StartGame) /*if state.can_start()*/ => {
-                debug_println!("The game should start or restart");
+                log_event!(Channel::Info, self.orchestrator.current_tick, None, "StartGame", "the game is resumed");
                 self.game_tick = GameTick::new(Duration::from_millis(1000));
                 self.state = GameState::Running;
                 
@@ -84,8 +117,15 @@ impl Game {
                 // self.orchestrator.start_all()?;
             }
 
+            (GameState::Paused, UiToGame::StepGame) => {
+                log_event!(Channel::Debug, self.orchestrator.current_tick, None, "StepGame", "advancing by one tick");
+                self.process_game_events()?;
+                self.orchestrator.handle_game_messages()?;
+                self.flush_ui_events();
+            }
+
             (GameState::Running, UiToGame::StopGame) /*if state.can_pause()*/ => {
-                debug_println!("The game should stop");
+                log_event!(Channel::Info, self.orchestrator.current_tick, None, "StopGame", "the game is paused");
                 self.state = GameState::Paused;
                 // self.orchestrator.stop_logic()?;
                 // self.state = GameState::Paused;
@@ -93,22 +133,155 @@ impl Game {
             }
 
             (_, UiToGame::ResetGame) => {
-                debug_println!("The game should reset");
+                log_event!(Channel::Info, self.orchestrator.current_tick, None, "ResetGame", "reset requested");
                 // self.reset_game()?;
             }
 
-            (_state, _msg) => {
-                debug_println!("Invalid command {:?} in state {:?}", _msg, _state);
+            (_, UiToGame::StartGame | UiToGame::StopGame | UiToGame::StepGame) => {
+                log_event!(Channel::Warning, self.orchestrator.current_tick, None, "InvalidCommand", "not allowed in state {:?}", self.state);
+            }
+
+            (_, UiToGame::SeekTick { .. }) => {
+                log_event!(Channel::Warning, self.orchestrator.current_tick, None, "InvalidCommand", "only a replay can seek a tick");
             }
+
+            (_, command) => {
+                self.handle_manual_command(command)?;
+            }
+
         }
 
         Ok(())
     }
 
-    fn asteroid_sunray_sender(&mut self) -> Result<(), String> {
+    /// Executes one of the commands used to manually interact with the simulation
+    ///
+    /// Only called by `handle_ui_command`, which handles every game flow command itself.
+    /// The outcome is always reported to the UI with a `GameToUi::CommandResult`,
+    /// an error of the command does not stop the game
+    fn handle_manual_command(&mut self, command: UiToGame) -> Result<(), OrchestratorError> {
+        let result = match command.clone() {
+            UiToGame::SpawnExplorer { explorer_id, planet_id } => self
+                .orchestrator
+                .spawn_explorer(explorer_id, planet_id)
+                .and_then(|_| match self.state {
+                    GameState::Running => self.orchestrator.start_explorer_ai(explorer_id),
+                    _ => Ok(()),
+                })
+                .map(|_| Some(CommandReply::ExplorerSpawned { explorer_id, planet_id })),
+            UiToGame::KillExplorer { explorer_id } => self
+                .orchestrator
+                .kill_explorer(explorer_id)
+                .map(|_| Some(CommandReply::ExplorerKilled { explorer_id })),
+            UiToGame::MoveExplorer { explorer_id, planet_id } => self
+                .orchestrator
+                .move_explorer(explorer_id, planet_id)
+                .map(|_| Some(CommandReply::ExplorerMoved { explorer_id, planet_id })),
+            UiToGame::SendSunray { planet_id } => self
+                .orchestrator
+                .send_sunray_to(planet_id)
+                .map(|_| Some(CommandReply::SunraySent { planet_id })),
+            UiToGame::SendAsteroid { planet_id } => self
+                .orchestrator
+                .send_asteroid_to(planet_id)
+                .map(|_| Some(CommandReply::AsteroidSent { planet_id })),
+            UiToGame::AddPlanet { planet_id, planet_type, neighbors } => self
+                .orchestrator
+                .add_planet_with_links(planet_id, planet_type, &neighbors)
+                .and_then(|_| match self.state {
+                    GameState::Running => self.orchestrator.start_planet_ai(planet_id),
+                    _ => Ok(()),
+                })
+                .map(|_| Some(CommandReply::PlanetAdded { planet_id })),
+            UiToGame::RemovePlanet { planet_id } => self
+                .orchestrator
+                .remove_planet(planet_id)
+                .map(|_| Some(CommandReply::PlanetRemoved { planet_id })),
+            UiToGame::RemoveLink { planet_one, planet_two } => self
+                .orchestrator
+                .remove_link(planet_one, planet_two)
+                .map(|_| Some(CommandReply::LinkRemoved { planet_one, planet_two })),
+            // The bag is sent to the UI when the explorer answers
+            UiToGame::BagDump { explorer_id } => self
+                .orchestrator
+                .request_bag_content(explorer_id)
+                .map(|_| None),
+            UiToGame::ExportTopology { format } => self
+                .orchestrator
+                .export_topology(format)
+                .map(|content| Some(CommandReply::TopologyExported { format, content })),
+            UiToGame::ExportMetrics { format } => self
+                .orchestrator
+                .metrics
+                .export(format)
+                .map_err(OrchestratorError::invalid)
+                .map(|content| Some(CommandReply::MetricsExported { format, content })),
+            UiToGame::SaveCheckpoint { path } => self
+                .orchestrator
+                .save_checkpoint(&path)
+                .map(|checkpoint| Some(CommandReply::CheckpointSaved { path, tick: checkpoint.tick })),
+            UiToGame::StartGame
+            | UiToGame::StopGame
+            | UiToGame::StepGame
+            | UiToGame::ResetGame
+            | UiToGame::EndGame
+            | UiToGame::SeekTick { .. } => {
+                unreachable!("game flow commands are dispatched by handle_ui_command")
+            }
+        };
+
+        match result {
+            Ok(Some(reply)) => self.notify_ui(GameToUi::CommandResult(Ok(reply))),
+            Ok(None) => {}
+            Err(error) => {
+                log_event!(Channel::Warning, self.orchestrator.current_tick, None, "CommandFailed", "{command:?}: {error}");
+                self.notify_ui(GameToUi::CommandResult(Err(CommandFailure {
+                    command,
+                    reason: error.to_string(),
+                    error,
+                })));
+            }
+        }
+        self.send_snapshot(true)
+    }
+
+    /// Sends a message to the UI, a closed UI must not stop the game
+    fn notify_ui(&self, msg: GameToUi) {
+        if self.sender_game_ui.send(msg).is_err() {
+            log_event!(Channel::Warning, self.orchestrator.current_tick, None, "NotifyUi", "unable to send messages to UI");
+        }
+    }
+
+    /// Forwards to the UI the events produced by the orchestrator
+    fn flush_ui_events(&mut self) {
+        while let Some(event) = self.orchestrator.ui_events.pop_front() {
+            self.notify_ui(event);
+        }
+    }
+
+    /// Waits for a UI command while the time of the game is stopped
+    ///
+    /// Explorers may still answer to manual commands (e.g. the bag dump),
+    /// so their messages are handled too
+    fn wait_ui_command(&mut self) -> Result<(), OrchestratorError> {
+        select! {
+            recv(self.receiver_game_ui) -> msg => {
+                let msg = msg.map_err(|_| OrchestratorError::disconnected(Actor::Ui))?;
+                self.handle_ui_command(msg)?;
+            }
+            recv(self.orchestrator.receiver_orch_explorer) -> msg => {
+                let msg = msg.map_err(|_| OrchestratorError::disconnected(Actor::AllExplorers))?;
+                self.orchestrator.handle_explorer_message(msg)?;
+            }
+        }
+        self.flush_ui_events();
+        Ok(())
+    }
+
+    fn asteroid_sunray_sender(&mut self) -> Result<(), OrchestratorError> {
         select! {
             recv(self.game_tick.ticker) -> _ => {
-                debug_println!("{:?}", self.game_tick.start_time.elapsed());
+                log_event!(Channel::Trace, self.orchestrator.current_tick, None, "Tick", "{:?} since the start", self.game_tick.start_time.elapsed());
                 self.process_game_events()?;
             }
             default => {
@@ -118,8 +291,11 @@ impl Game {
         Ok(())
     }
 
-    fn process_game_events(&mut self) -> Result<(), String> {
-        // debug_println!("{:?}", self.ticker);
+    fn process_game_events(&mut self) -> Result<(), OrchestratorError> {
+        self.orchestrator.advance_tick();
+        //Every tick is also the heartbeat of the planets: crashed ones and the ones late with their acks are found here
+        self.orchestrator.check_planet_threads()?;
+        self.orchestrator.check_planet_deadlines(std::time::Instant::now())?;
         match settings::pop_sunray_asteroid_sequence() {
             Some('S') => {
                 self.orchestrator.send_sunray_to_all()?;
@@ -129,10 +305,43 @@ impl Game {
             }
             msg => {
                 // Probability mode
-                println!("{:?}", msg);
+                log_event!(Channel::Trace, self.orchestrator.current_tick, None, "Sequence", "no event in the sequence ({msg:?}), sending sunrays");
                 self.orchestrator.send_sunray_to_all()?;
             }
         }
+        self.send_snapshot(true)?;
+        // The bags are needed to know if a resource was crafted, the answers arrive with the next ticks
+        if self.end_conditions.iter().any(|condition| matches!(condition, EndCondition::ResourceCrafted { .. })) {
+            self.orchestrator.request_explorer_bags();
+        }
+        if let Some(reason) = self.orchestrator.check_end_conditions(&self.end_conditions) {
+            self.end_game(reason)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the metrics of the game to the file of `settings::set_metrics_file`, if any
+    fn save_metrics(&self) {
+        let Some(path) = settings::get_metrics_file() else {
+            return;
+        };
+        // The game ends anyway, the problem is only logged
+        if let Err(e) = self.orchestrator.metrics.save(&path) {
+            log_event!(Channel::Warning, self.orchestrator.current_tick, None, "Metrics", "{e}");
+        }
+    }
+
+    /// Sends the final scoreboard to the UI and kills every planet
+    fn end_game(&mut self, reason: GameOverReason) -> Result<(), OrchestratorError> {
+        log_event!(Channel::Info, self.orchestrator.current_tick, None, "GameOver", "{reason:?}");
+        // The scores are computed on the bags of the end of the game
+        self.orchestrator.collect_explorer_bags()?;
+        self.flush_ui_events();
+        let scoreboard = self.orchestrator.scoreboard(reason);
+        self.notify_ui(GameToUi::GameOver { scoreboard });
+        self.save_metrics();
+        self.orchestrator.send_planet_kill_to_all()?;
+        self.game_over = true;
         Ok(())
     }
 }
@@ -142,11 +351,20 @@ pub fn run_with_ui(
     file_path: String,
     sender_game_ui: Sender<GameToUi>,
     receiver_game_ui: Receiver<UiToGame>,
-) -> Result<(), String> {
-    // Initialize orchestrator
-    let mut orchestrator = Orchestrator::new()?;
+) -> Result<(), OrchestratorError> {
+    let definition = GalaxyDefinition::from_file(file_path.as_str().trim())
+        .map_err(|problem| OrchestratorError::InitFile { problems: vec![problem] })?;
+    run_with_ui_definition(definition, sender_game_ui, receiver_game_ui)
+}
 
-    orchestrator.initialize_galaxy_by_file(file_path.as_str().trim())?;
+/// Entry point for running a galaxy that is not read from a file, e.g. one built by `GalaxyGenerator`
+pub fn run_with_ui_definition(
+    definition: GalaxyDefinition,
+    sender_game_ui: Sender<GameToUi>,
+    receiver_game_ui: Receiver<UiToGame>,
+) -> Result<(), OrchestratorError> {
+    // Initialize orchestrator
+    let orchestrator = Orchestrator::from_definition(definition)?;
 
     // Create and run game loop
     let game_loop = Game::new(orchestrator, receiver_game_ui, sender_game_ui);
@@ -154,44 +372,164 @@ pub fn run_with_ui(
     game_loop.run()
 }
 
+/// Entry point for going on with a game saved with `UiToGame::SaveCheckpoint`
+///
+/// The game waits for StartGame like a new one, see `GalaxyCheckpoint` for what is restored
+pub fn run_from_checkpoint(
+    checkpoint_path: String,
+    sender_game_ui: Sender<GameToUi>,
+    receiver_game_ui: Receiver<UiToGame>,
+) -> Result<(), OrchestratorError> {
+    let checkpoint = GalaxyCheckpoint::from_file(checkpoint_path.trim())
+        .map_err(|reason| OrchestratorError::Checkpoint { reason })?;
+    let orchestrator = Orchestrator::from_checkpoint(checkpoint)?;
+    Game::new(orchestrator, receiver_game_ui, sender_game_ui).run()
+}
+
+/// Entry point for watching a finished game again, see `GalaxyReplay`
+///
+/// No planet or explorer is started: StartGame plays one tick per second, StopGame pauses,
+/// StepGame advances one tick, ResetGame goes back to the start and SeekTick jumps to a tick
+pub fn run_replay(
+    journal_path: String,
+    sender_game_ui: Sender<GameToUi>,
+    receiver_game_ui: Receiver<UiToGame>,
+) -> Result<(), OrchestratorError> {
+    let replay =
+        GalaxyReplay::from_file(journal_path.trim()).map_err(|reason| OrchestratorError::Journal { reason })?;
+    ReplayGame::new(replay, receiver_game_ui, sender_game_ui).run()
+}
+
+/// Plays a journal back to the UI
+struct ReplayGame {
+    replay: GalaxyReplay,
+    state: GameState,
+    // Tick shown to the UI
+    current_tick: u64,
+    game_tick: GameTick,
+    receiver_game_ui: Receiver<UiToGame>,
+    sender_game_ui: Sender<GameToUi>,
+}
+
+impl ReplayGame {
+    fn new(replay: GalaxyReplay, receiver_game_ui: Receiver<UiToGame>, sender_game_ui: Sender<GameToUi>) -> Self {
+        Self {
+            replay,
+            state: GameState::WaitingStart,
+            current_tick: 0,
+            game_tick: GameTick::new(Duration::from_millis(1000)),
+            receiver_game_ui,
+            sender_game_ui,
+        }
+    }
+
+    fn run(mut self) -> Result<(), OrchestratorError> {
+        self.send_snapshot();
+        loop {
+            if self.state == GameState::Running {
+                select! {
+                    recv(self.receiver_game_ui) -> msg => {
+                        let msg = msg.map_err(|_| OrchestratorError::disconnected(Actor::Ui))?;
+                        self.handle_ui_command(msg)?;
+                    }
+                    recv(self.game_tick.ticker) -> _ => {
+                        // The replay stops by itself on the last tick of the journal
+                        if self.current_tick < self.replay.last_tick() {
+                            self.seek(self.current_tick + 1);
+                        } else {
+                            self.state = GameState::Paused;
+                        }
+                    }
+                }
+            } else {
+                let msg = self
+                    .receiver_game_ui
+                    .recv()
+                    .map_err(|_| OrchestratorError::disconnected(Actor::Ui))?;
+                self.handle_ui_command(msg)?;
+            }
+        }
+    }
+
+    fn handle_ui_command(&mut self, msg: UiToGame) -> Result<(), OrchestratorError> {
+        match msg {
+            UiToGame::EndGame => return Err(OrchestratorError::GameEnded),
+            UiToGame::StartGame => {
+                self.game_tick = GameTick::new(Duration::from_millis(1000));
+                self.state = GameState::Running;
+            }
+            UiToGame::StopGame => self.state = GameState::Paused,
+            UiToGame::StepGame => self.seek(self.current_tick + 1),
+            UiToGame::ResetGame => self.seek(0),
+            UiToGame::SeekTick { tick: target } => self.seek(target),
+            command => {
+                let error = OrchestratorError::invalid("A replay cannot be changed");
+                self.notify_ui(GameToUi::CommandResult(Err(CommandFailure {
+                    command,
+                    reason: error.to_string(),
+                    error,
+                })));
+            }
+        }
+        Ok(())
+    }
+
+    // Ticks after the end of the journal show the last one
+    fn seek(&mut self, target: u64) {
+        let target = target.min(self.replay.last_tick());
+        if target != self.current_tick {
+            self.current_tick = target;
+            self.send_snapshot();
+        }
+    }
+
+    fn send_snapshot(&self) {
+        self.notify_ui(GameToUi::GameStatusUpdate {
+            snapshot: self.replay.snapshot_at(self.current_tick),
+        });
+    }
+
+    fn notify_ui(&self, msg: GameToUi) {
+        if self.sender_game_ui.send(msg).is_err() {
+            log_event!(Channel::Warning, self.current_tick, None, "NotifyUi", "unable to send messages to UI");
+        }
+    }
+}
+
 /// Core game loop structure
 impl Game {
-    pub fn run(mut self) -> Result<(), String> {
-        loop {
+    pub fn run(mut self) -> Result<(), OrchestratorError> {
+        while !self.game_over {
             match self.state {
                 GameState::WaitingStart => self.waiting_loop()?,
                 GameState::Running => self.running_loop()?,
                 GameState::Paused => self.paused_loop()?,
             }
         }
+        Ok(())
     }
 
     /// Loop dedicato esclusivamente alla fase di attesa iniziale
-    fn waiting_loop(&mut self) -> Result<(), String> {
+    fn waiting_loop(&mut self) -> Result<(), OrchestratorError> {
         // Qui non facciamo calcoli di tempo, aspettiamo solo lo Start
-        let msg = self
-            .receiver_game_ui
-            .recv()
-            .map_err(|_| "UI Channel Error")?;
-
-        self.handle_ui_command(msg)?;
-        Ok(())
+        self.wait_ui_command()
     }
 
     /// Loop ad alte prestazioni: gestione tick e orchestrator
-    fn running_loop(&mut self) -> Result<(), String> {
+    fn running_loop(&mut self) -> Result<(), OrchestratorError> {
         self.game_tick = GameTick::new(Duration::from_millis(1000));
 
-        while self.state == GameState::Running {
+        while self.state == GameState::Running && !self.game_over {
             select_biased! {
                 recv(self.receiver_game_ui) -> msg => {
-                    let msg = msg.map_err(|_| "UI Error")?;
+                    let msg = msg.map_err(|_| OrchestratorError::disconnected(Actor::Ui))?;
                     self.handle_ui_command(msg)?;
                 }
                 default => {
 
                     self.asteroid_sunray_sender()?;
                     self.orchestrator.handle_game_messages()?;
+                    self.flush_ui_events();
 
                     // Sleep ridotto per massima reattività
                     std::thread::sleep(Duration::from_millis(2));
@@ -202,14 +540,11 @@ impl Game {
     }
 
     /// Loop di pausa: consuma solo messaggi UI, tempo fermo
-    fn paused_loop(&mut self) -> Result<(), String> {
-        debug_println!("Game is paused. Waiting for resume...");
+    fn paused_loop(&mut self) -> Result<(), OrchestratorError> {
+        log_event!(Channel::Debug, self.orchestrator.current_tick, None, "Paused", "waiting for resume");
 
         // Qui usiamo una recv() bloccante: non c'è bisogno di loopare a vuoto
         // perché il tempo di gioco è fermo.
-        let msg = self.receiver_game_ui.recv().map_err(|_| "UI Error")?;
-        self.handle_ui_command(msg)?;
-
-        Ok(())
+        self.wait_ui_command()
     }
 }
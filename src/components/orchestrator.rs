@@ -1,11 +1,25 @@
 use crate::components::explorer::{BagType, Explorer};
-use crate::utils::{ExplorerStatus, PlanetStatus};
-use crate::utils::registry::PlanetType::{
-    BlackAdidasShoe, Ciuc, HoustonWeHaveABorrow, ImmutableCosmicBorrow, OneMillionCrabs, Rustrelli,
+use crate::messages::{CommandReply, GameToUi};
+use crate::utils::{
+    ExplorerSnapshot, ExplorerStatus, GalaxySnapshot, PlanetSnapshot, PlanetStatus,
+    TopologySnapshot,
 };
-use crate::utils::registry::{PLANET_REGISTRY, PlanetType};
+use crate::settings;
+use crate::utils::checkpoint::{ExplorerCheckpoint, GalaxyCheckpoint, PlanetCheckpoint, PlanetStateSummary};
+use crate::utils::errors::{Actor, OrchestratorError};
+use crate::utils::export::ExportFormat;
+use crate::utils::metrics::GameMetrics;
+use crate::utils::scoring::{EndCondition, ExplorerScore, GameOverReason, PlanetScore, Scoreboard};
+use crate::messages::json::resource_types::resource_name;
+use crate::utils::journal::{Journal, JournalEvent};
+use crate::utils::logging::{log_event, publish_tick};
+use crate::utils::galaxy_file::GalaxyDefinition;
+use crate::utils::registry::PlanetType;
 use crate::utils::state_enums::Status;
-use crate::utils::types::GalaxyTopology;
+use crate::utils::galaxy_graph::GalaxyGraph;
+use crate::utils::galaxy_validation::Diagnostic;
+use crate::utils::types::{GalaxyTopology, PlanetConfig};
+use crate::utils::topology_analysis::{SharedTopologyAnalysis, TopologyAnalysis};
 use common_game::components::forge::Forge;
 use common_game::logging::Channel;
 use common_game::protocols::orchestrator_explorer::{
@@ -13,14 +27,18 @@ use common_game::protocols::orchestrator_explorer::{
 };
 use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
 use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
-use crossbeam_channel::{Receiver, Sender, select, tick, unbounded};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, select, tick, unbounded};
 use rustc_hash::FxHashMap;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::any::Any;
 use std::sync::Arc;
 use std::sync::RwLock;
-use std::time::{Duration};
-use std::{fs, thread};
+use std::time::{Duration, Instant};
+use std::thread;
+use std::thread::JoinHandle;
 
 const LOG_FN_CALL_CHNL: Channel = Channel::Debug;
 const LOG_FN_INT_OPERATIONS: Channel = Channel::Trace;
@@ -28,31 +46,61 @@ const LOG_ACTORS_ACTIVITY: Channel = Channel::Info;
 
 const TIMEOUT_DURATION: Duration = Duration::from_millis(2000);
 
-#[cfg(feature = "debug-prints")]
-#[macro_export]
-macro_rules! debug_println {
-    ($($arg:tt)*) => { println!($($arg)*) };
-}
-
-#[cfg(not(feature = "debug-prints"))]
-#[macro_export]
-macro_rules! debug_println {
-    ($($arg:tt)*) => {
-        ()
-    };
-}
-
 pub struct Orchestrator {
     // Forge sunray and asteroid
     pub forge: Forge,
 
     //Galaxy
     pub galaxy_topology: GalaxyTopology,
-    pub galaxy_lookup: FxHashMap<u32, (u32, PlanetType)>,
+    //Type of every planet of the galaxy
+    pub galaxy_lookup: FxHashMap<u32, PlanetType>,
+    //Factory parameters of the planets that have a config in the galaxy file
+    pub planet_configs: FxHashMap<u32, PlanetConfig>,
 
     //Status for each planets and explorers, BTreeMaps are useful for printing
     pub planets_status: PlanetStatus,
     pub explorer_status: ExplorerStatus,
+    //Planet on which every explorer currently is
+    pub explorer_positions: BTreeMap<u32, u32>,
+    //Links removed during the game, as (lower id, higher id)
+    pub destroyed_links: BTreeSet<(u32, u32)>,
+    //Critical planets and distances, shared with the explorers
+    pub topology_analysis: SharedTopologyAnalysis,
+
+    //Deadlines of the requests every planet has not acknowledged yet, oldest first
+    pub pending_acks: BTreeMap<u32, VecDeque<Instant>>,
+    //Planets that miss a deadline are killed and isolated instead of only being marked as unresponsive
+    pub kill_unresponsive_planets: bool,
+
+    //Threads of the planets, joined when they end to find out why
+    pub planet_threads: HashMap<u32, JoinHandle<Result<(), String>>>,
+    //Panic message (or error) of every crashed planet
+    pub planet_crashes: BTreeMap<u32, String>,
+
+    //Last internal state and bag received from every planet and explorer, saved in the checkpoints
+    pub planet_states: BTreeMap<u32, PlanetStateSummary>,
+    pub explorer_bags: BTreeMap<u32, BagType>,
+    //Bags saved in the checkpoint the galaxy was restored from, the explorers start again empty
+    //so they are kept apart from the bags of this game (scores, crafted resources)
+    pub restored_bags: BTreeMap<u32, BagType>,
+    //Sunrays owed to the planets restored from a checkpoint, sent when the game starts
+    pub(crate) pending_recharges: BTreeMap<u32, usize>,
+    //Explorers whose bag was asked by the UI, the other answers are only cached
+    pub(crate) ui_bag_requests: BTreeSet<u32>,
+
+    //Tick in which every planet was created and died, used by the scoreboard
+    pub planet_birth_ticks: BTreeMap<u32, u64>,
+    pub planet_death_ticks: BTreeMap<u32, u64>,
+    //Counters of every tick and ack latency of every planet
+    pub metrics: GameMetrics,
+
+    //Append-only record of the game, see settings::set_journal_file
+    pub(crate) journal: Option<Journal>,
+
+    //Increased on every change of the galaxy, used to version the UI snapshots
+    pub state_version: u64,
+    pub current_tick: u64,
+
     //Communication channels for sending messages to planets and explorers
     pub planet_channels: HashMap<u32, (Sender<OrchestratorToPlanet>, Sender<ExplorerToPlanet>)>,
     pub explorer_channels: HashMap<u32, (Sender<OrchestratorToExplorer>, Sender<PlanetToExplorer>)>,
@@ -64,6 +112,9 @@ pub struct Orchestrator {
     //Channel to clone for the explorer and for receiving Explorer Messages
     pub sender_explorer_orch: Sender<ExplorerToOrchestrator<BagType>>,
     pub receiver_orch_explorer: Receiver<ExplorerToOrchestrator<BagType>>,
+
+    //Events produced while handling planets and explorers messages, forwarded to the UI by the game
+    pub ui_events: VecDeque<GameToUi>,
 }
 
 //Initialization game functions
@@ -73,55 +124,76 @@ impl Orchestrator {
     /// Function used as shorthand to create a new
     /// galaxy topology instance
     fn new_gtop() -> GalaxyTopology {
-        //TODO implement proper debug. channel: LOG_FN_CALL_CHNL
-
-        Arc::new(RwLock::new(Vec::new()))
+        Arc::new(RwLock::new(GalaxyGraph::new()))
     }
 
     //Check and init orchestrator for the test, the comms with the ui are fake
-    pub(crate) fn new() -> Result<Self, String> {
-        //TODO implement proper debug. channel: LOG_FN_CALL_CHNL
-
+    pub(crate) fn new() -> Result<Self, OrchestratorError> {
+        log_event!(LOG_FN_CALL_CHNL, 0, None, "OrchestratorNew", "creating the orchestrator");
         let (sender_planet_orch, recevier_orch_planet) = unbounded();
         let (sender_explorer_orch, receiver_orch_explorer) = unbounded();
+        let journal = settings::get_journal_file()
+            .map(|path| Journal::create(&path))
+            .transpose()
+            .map_err(|reason| OrchestratorError::Journal { reason })?;
 
         let new_orch = Self {
-            forge: Forge::new()?,
+            forge: Forge::new().map_err(|reason| OrchestratorError::Forge { reason })?,
             galaxy_topology: Self::new_gtop(),
             galaxy_lookup: FxHashMap::default(),
+            planet_configs: FxHashMap::default(),
             planets_status: Arc::new(RwLock::new(BTreeMap::new())),
             explorer_status: Arc::new(RwLock::new(BTreeMap::new())),
+            explorer_positions: BTreeMap::new(),
+            destroyed_links: BTreeSet::new(),
+            topology_analysis: Arc::new(RwLock::new(TopologyAnalysis::default())),
+            pending_acks: BTreeMap::new(),
+            kill_unresponsive_planets: settings::get_kill_unresponsive_planets(),
+            planet_threads: HashMap::new(),
+            planet_crashes: BTreeMap::new(),
+            planet_states: BTreeMap::new(),
+            explorer_bags: BTreeMap::new(),
+            restored_bags: BTreeMap::new(),
+            pending_recharges: BTreeMap::new(),
+            ui_bag_requests: BTreeSet::new(),
+            planet_birth_ticks: BTreeMap::new(),
+            planet_death_ticks: BTreeMap::new(),
+            metrics: GameMetrics::new(),
+            journal,
+            state_version: 0,
+            current_tick: 0,
             planet_channels: HashMap::new(),
             explorer_channels: HashMap::new(),
             sender_planet_orch,
             recevier_orch_planet,
             sender_explorer_orch,
             receiver_orch_explorer,
+            ui_events: VecDeque::new(),
         };
         Ok(new_orch)
     }
 
-    pub(crate) fn reset(&mut self) -> Result<(), String> {
-        //TODO implement proper debug. channel: INFO. LOG_FN_CALL_CHNL. start
+    pub(crate) fn reset(&mut self) -> Result<(), OrchestratorError> {
+        log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, None, "Reset", "killing every planet");
 
         //send a message every 2000 millis to the ticker receiver
         let timeout = tick(TIMEOUT_DURATION);
         //Kill every thread
         self.send_planet_kill_to_all()?;
         loop {
-            //TODO implement proper debug. channel: LOG_FN_INT_OPERATIONS
             select! {
                 recv(self.recevier_orch_planet)->msg=>{
                     let msg_unwraped = match msg{
                         Ok(res)=>res,
-                        Err(_)=>return Err("No more sender connected and no messages in the buffer".to_string()),
+                        Err(_)=>return Err(OrchestratorError::disconnected(Actor::AllPlanets)),
                     };
                     match msg_unwraped{
                         PlanetToOrchestrator::KillPlanetResult { planet_id }=>{
-                            self.planets_status.write().unwrap().insert(planet_id, Status::Dead);
+                            log_event!(LOG_FN_INT_OPERATIONS, self.current_tick, Some(Actor::Planet(planet_id)), "KillPlanetResult", "killed by the reset");
+                            self.set_planet_status(planet_id, Status::Dead);
                             let mut planet_alive=false;
                             for (_, state) in self.planets_status.read().unwrap().iter(){
-                                if *state != Status::Dead{
+                                if !state.is_dead() && *state != Status::Unresponsive{
                                     planet_alive=true;
                                     break;
                                 }
@@ -134,11 +206,17 @@ impl Orchestrator {
                     }
                 }
                 recv(timeout)->_msg=>{
-                    //After one second every planet should have been killed
-                    for (_, state) in self.planets_status.read().unwrap().iter(){
-                        if *state != Status::Dead{
-                            return Err("Not every planet is being killed".to_string());
-                        }
+                    //After one second every planet should have been killed, hung planets cannot answer
+                    let planet_ids: Vec<u32> = self
+                        .planets_status
+                        .read()
+                        .unwrap()
+                        .iter()
+                        .filter(|(_, state)| !state.is_dead() && **state != Status::Unresponsive)
+                        .map(|(planet_id, _)| *planet_id)
+                        .collect();
+                    if !planet_ids.is_empty() {
+                        return Err(OrchestratorError::Timeout { planet_ids });
                     }
                     break;
                 }
@@ -149,10 +227,31 @@ impl Orchestrator {
         self.galaxy_topology = Self::new_gtop();
         self.planets_status = Arc::new(RwLock::new(BTreeMap::new()));
         self.explorer_status = Arc::new(RwLock::new(BTreeMap::new()));
+        self.explorer_positions = BTreeMap::new();
+        self.destroyed_links = BTreeSet::new();
+        self.pending_acks = BTreeMap::new();
+        //Threads that did not stop are detached, they belong to the previous game
+        self.planet_threads = HashMap::new();
+        self.planet_crashes = BTreeMap::new();
+        self.planet_states = BTreeMap::new();
+        self.explorer_bags = BTreeMap::new();
+        self.restored_bags = BTreeMap::new();
+        self.pending_recharges = BTreeMap::new();
+        self.ui_bag_requests = BTreeSet::new();
+        self.planet_birth_ticks = BTreeMap::new();
+        self.planet_death_ticks = BTreeMap::new();
+        self.metrics = GameMetrics::new();
+        //A new lock, explorers of the previous game may still hold the old one
+        self.topology_analysis = Arc::new(RwLock::new(TopologyAnalysis::default()));
         self.planet_channels = HashMap::new();
         self.explorer_channels = HashMap::new();
+        self.current_tick = 0;
+        publish_tick(0);
+        //The version is never reset, otherwise the UI could skip the first frames of the new game
+        self.bump_version();
+        self.record(JournalEvent::Reset);
+        log_event!(LOG_ACTORS_ACTIVITY, 0, None, "Reset", "the orchestrator is ready for a new game");
         Ok(())
-        //TODO implement proper debug. channel: LOG_FN_CALL_CHNL. finish
     }
 
     ///initialize communication channels for planets
@@ -164,15 +263,12 @@ impl Orchestrator {
         Sender<ExplorerToPlanet>,
         Receiver<ExplorerToPlanet>,
     ) {
-        //TODO implement proper debug. channel: LOG_FN_CALL_CHNL
-        //TODO implement proper debug. channel: LOG_FN_INT_OPERATIONS
         //orch-planet
         let (sender_orch, receiver_orch): (
             Sender<OrchestratorToPlanet>,
             Receiver<OrchestratorToPlanet>,
         ) = unbounded();
 
-        //TODO implement proper debug. channel: LOG_FN_INT_OPERATIONS
         //explorer-planet
         let (sender_explorer, receiver_explorer): (
             Sender<ExplorerToPlanet>,
@@ -202,15 +298,11 @@ impl Orchestrator {
         Sender<PlanetToExplorer>,
         Receiver<PlanetToExplorer>,
     ) {
-        //TODO implement proper debug. channel: LOG_FN_CALL_CHNL
-
-        //TODO implement proper debug. channel: LOG_FN_INT_OPERATIONS
         let (sender_orch, receiver_orch): (
             Sender<OrchestratorToExplorer>,
             Receiver<OrchestratorToExplorer>,
         ) = unbounded();
 
-        //TODO implement proper debug. channel: LOG_FN_INT_OPERATIONS
         let (sender_planet, receiver_planet): (
             Sender<PlanetToExplorer>,
             Receiver<PlanetToExplorer>,
@@ -218,8 +310,8 @@ impl Orchestrator {
 
         (sender_orch, receiver_orch, sender_planet, receiver_planet)
     }
-    pub(crate) fn add_planet(&mut self, id: u32, type_id: PlanetType) -> Result<(), String> {
-        //TODO implement proper debug. channel: LOG_FN_CALL_CHNL
+    pub(crate) fn add_planet(&mut self, id: u32, type_id: PlanetType) -> Result<(), OrchestratorError> {
+        log_event!(LOG_FN_CALL_CHNL, self.current_tick, Some(Actor::Planet(id)), "AddPlanet", "type {type_id}");
         //Init comms OrchestratorToPlanet, ExplorerToPlanet
         let (sender_orchestrator, receiver_orchestrator, sender_explorer, receiver_explorer) =
             Orchestrator::init_comms_planet();
@@ -228,24 +320,27 @@ impl Orchestrator {
         let planet_to_orchestrator_channels =
             (receiver_orchestrator, self.sender_planet_orch.clone());
 
-        //TODO implement proper debug. channel: LOG_ACTORS_ACTIVITY
         //creation of the planet
+        let config = self.planet_configs.get(&id).unwrap_or(&PlanetConfig::Null);
+        let mut new_planet = type_id
+            .create_planet(
+                planet_to_orchestrator_channels.0,
+                planet_to_orchestrator_channels.1,
+                receiver_explorer,
+                id,
+                config,
+            )
+            .map_err(|reason| OrchestratorError::PlanetCreation { planet_id: id, reason })?;
 
-        let mut new_planet = (PLANET_REGISTRY.get(&type_id).unwrap().as_ref())(
-            planet_to_orchestrator_channels.0,
-            planet_to_orchestrator_channels.1,
-            receiver_explorer,
-            id,
-        )?;
-
-        //TODO implement proper debug. channel: LOG_FN_INT_OPERATIONS
         //Update HashMaps
-        self.planets_status.write().unwrap().insert(new_planet.id(), Status::Paused);
+        self.set_planet_status(new_planet.id(), Status::Paused);
+        self.planet_birth_ticks.insert(id, self.current_tick);
         self.planet_channels
             .insert(new_planet.id(), (sender_orchestrator, sender_explorer));
 
-        debug_println!("Start planet{id} thread");
-        thread::spawn(move || -> Result<(), String> { new_planet.run() });
+        log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Planet(id)), "PlanetCreated", "{type_id} planet thread started");
+        let handle = thread::spawn(move || -> Result<(), String> { new_planet.run() });
+        self.planet_threads.insert(id, handle);
         Ok(())
     }
     pub(crate) fn add_explorer(
@@ -266,129 +361,117 @@ impl Orchestrator {
             (receiver_orch, self.sender_explorer_orch.clone()),
             (receiver_planet, sender_explorer),
             free_cells,
-        );
+        )
+        .with_topology_analysis(Arc::clone(&self.topology_analysis));
 
         //Update HashMaps
-        self.explorer_status
-            .write()
-            .unwrap()
-            .insert(new_explorer.id(), Status::Paused);
+        self.set_explorer_status(new_explorer.id(), Status::Paused);
+        self.explorer_positions.insert(new_explorer.id(), planet_id);
+        self.record(JournalEvent::ExplorerSpawned { explorer_id, planet_id });
         self.explorer_channels
             .insert(new_explorer.id(), (sender_orch, sender_planet));
 
         // self.explorers.push(explorer);
         //Spawn the corresponding thread for the explorer
-        thread::spawn(|| -> Result<(), String> {
-            let _ = new_explorer; //TODO implement a run function for explorer to interact with orchestrator
-            Ok(())
+        thread::spawn(move || {
+            let mut new_explorer = new_explorer;
+            new_explorer.run();
         });
     }
-    pub(crate) fn initialize_galaxy_by_file(&mut self, path: &str) -> Result<(), String> {
-        //At the moment are allowed only consecutive id from 0 to MAX u32
-
-        //Read the input file and handle it
-        let input = fs::read_to_string(path)
-            .map_err(|_| format!("Unable to read the input from {path}"))?;
-
-        let mut adj_list_for_topology = Vec::new();
-
-        let mut new_lookup: FxHashMap<u32, (u32, PlanetType)> = FxHashMap::default();
+    /// Creates an orchestrator with the galaxy already initialized, ready to be given to the game
+    pub(crate) fn from_definition(definition: GalaxyDefinition) -> Result<Self, OrchestratorError> {
+        let mut orchestrator = Self::new()?;
+        orchestrator.initialize_galaxy_by_definition(definition)?;
+        Ok(orchestrator)
+    }
 
-        for (line_num, line) in input.lines().enumerate() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
+    pub(crate) fn initialize_galaxy_by_file(&mut self, path: &str) -> Result<(), OrchestratorError> {
+        //Read the input file and handle it, the format depends on the extension
+        let definition = GalaxyDefinition::from_file(path).map_err(|problem| OrchestratorError::InitFile {
+            problems: vec![problem],
+        })?;
+        self.initialize_galaxy_by_definition(definition)
+    }
 
-            // Split at comma and u32 conversion
-            let values: Vec<u32> = line
-                .split(',')
-                .map(|s| {
-                    s.trim().parse::<u32>().map_err(|_| {
-                        format!("Error row {}: value '{}' is not a u32", line_num + 1, s)
-                    })
-                })
-                .collect::<Result<Vec<u32>, String>>()?;
+    /// Creates planets, topology and explorers described by a galaxy definition
+    pub(crate) fn initialize_galaxy_by_definition(
+        &mut self,
+        definition: GalaxyDefinition,
+    ) -> Result<(), OrchestratorError> {
+        //Every problem is reported at once, before any planet is created
+        let (errors, warnings): (Vec<_>, Vec<_>) = definition
+            .validate(settings::get_warn_disconnected_galaxy())
+            .into_iter()
+            .partition(|diagnostic| diagnostic.is_error());
+        for warning in &warnings {
+            log_event!(Channel::Warning, 0, None, "GalaxyFile", "{warning}");
+        }
+        if !errors.is_empty() {
+            return Err(OrchestratorError::InitFile { problems: errors });
+        }
 
-            if values.len() < 2 {
-                return Err(format!("Row {}: ID or Type missing", line_num + 1));
+        let mut neighbors = BTreeMap::new();
+        let mut new_lookup: FxHashMap<u32, PlanetType> = FxHashMap::default();
+        let mut new_configs: FxHashMap<u32, PlanetConfig> = FxHashMap::default();
+
+        for planet in &definition.planets {
+            let planet_type = PlanetType::from_name(&planet.planet_type).ok_or(OrchestratorError::InitFile {
+                problems: vec![Diagnostic::error(
+                    planet.line,
+                    format!("planet {}: unknown planet type '{}'", planet.id, planet.planet_type),
+                )],
+            })?;
+
+            new_lookup.insert(planet.id, planet_type);
+            if !planet.config.is_null() {
+                new_configs.insert(planet.id, planet.config.clone());
             }
+            neighbors.insert(planet.id, planet.neighbors.clone());
+        }
+        self.galaxy_lookup = new_lookup;
+        self.planet_configs = new_configs;
 
-            let node_id = values[0];
-            let node_type = values[1];
-            let neighbors = &values[2..];
-
-            //saving id-index to lookup table
-            new_lookup.insert(
-                node_id,
-                (
-                    line_num as u32,
-                    match node_type {
-                        0 => BlackAdidasShoe,
-                        1 => Ciuc,
-                        2 => HoustonWeHaveABorrow,
-                        3 => ImmutableCosmicBorrow,
-                        4 => OneMillionCrabs,
-                        5 => Rustrelli,
-                        6 => Rustrelli,
-                        _ => PlanetType::random(),
-                    },
-                ),
-            );
+        if let Some(events) = definition.events {
+            settings::set_sunray_asteroid_sequence(events);
+        }
 
-            let mut adj_row = vec![];
-            adj_row.extend_from_slice(neighbors);
+        //Initialize the orchestrator galaxy topology
+        self.initialize_galaxy_by_neighbors(neighbors)?;
 
-            adj_list_for_topology.push(adj_row);
+        for explorer in definition.explorers {
+            self.spawn_explorer(explorer.id, explorer.planet_id)?;
         }
-        for row in &mut adj_list_for_topology {
-            for node in row {
-                if let Some(&(new_idx, _)) = new_lookup.get(node) {
-                    *node = new_idx;
-                }
-            }
-        }
-        self.galaxy_lookup = new_lookup;
-        //Initialize the orchestrator galaxy topology
-        self.initialize_galaxy_by_adj_list(adj_list_for_topology)?;
 
         Ok(())
     }
 
+    /// Shorthand used by the tests: the row index is the planet id
     pub(crate) fn initialize_galaxy_by_adj_list(
         &mut self,
         adj_list: Vec<Vec<u32>>,
-    ) -> Result<(), String> {
-        let num_planets = adj_list.len();
-        //Print the result
-        debug_println!("Init file content:");
-        adj_list
-            .iter()
-            .for_each(|_row| debug_println!("{:?}", _row));
-
-        //Initialize matrix of adjecencies
-        let mut new_topology: Vec<Vec<bool>> = Vec::new();
+    ) -> Result<(), OrchestratorError> {
+        let neighbors = adj_list
+            .into_iter()
+            .enumerate()
+            .map(|(id, row)| (id as u32, row))
+            .collect();
+        self.initialize_galaxy_by_neighbors(neighbors)
+    }
 
-        for _ in 0..num_planets {
-            let v = vec![false; num_planets];
-            new_topology.push(v);
+    /// Builds the topology from the neighbors of every planet and creates the planets of `galaxy_lookup`
+    pub(crate) fn initialize_galaxy_by_neighbors(
+        &mut self,
+        neighbors: BTreeMap<u32, Vec<u32>>,
+    ) -> Result<(), OrchestratorError> {
+        for (planet_id, row) in &neighbors {
+            log_event!(LOG_FN_INT_OPERATIONS, 0, Some(Actor::Planet(*planet_id)), "InitFileRow", "neighbors {row:?}");
         }
-        debug_println!("empty adj matrix:");
-        new_topology
-            .iter()
-            .for_each(|_row| debug_println!("{:?}", _row));
 
-        for (idx, row) in adj_list.iter().enumerate() {
-            for conn in row.iter() {
-                new_topology[idx][*conn as usize] = true;
-                new_topology[*conn as usize][idx] = true;
-            }
-        }
+        let new_topology = GalaxyGraph::from_neighbors(&neighbors).map_err(|e| OrchestratorError::InitFile {
+            problems: vec![Diagnostic::error(None, e)],
+        })?;
 
-        debug_println!("full adj matrix:");
-        new_topology
-            .iter()
-            .for_each(|_row| debug_println!("{:?}", _row));
+        log_event!(LOG_FN_INT_OPERATIONS, 0, None, "GalaxyLinks", "{:?}", new_topology.links());
 
         //Update orchestrator topology
 
@@ -400,129 +483,301 @@ impl Orchestrator {
                 Ok(())
             }
             Err(_e) => {
-                debug_println!("ERROR galaxy topology lock failed.");
+                log_event!(Channel::Error, self.current_tick, None, "LockPoisoned", "galaxy topology");
                 Err(())
             }
         };
 
         if lock_try.is_ok() {
+            self.bump_version();
             //Initialize all the planets give the list of ids
             let ids_list: Vec<u32> = self.galaxy_lookup.keys().map(|x| x.clone()).collect(); //Every row should have at least one ids
             self.initialize_planets_by_ids_list(ids_list.clone())?;
+            self.refresh_topology_analysis()?;
+            self.record_galaxy_loaded();
             Ok(())
         } else {
-            Err("rwlock error".to_string())
+            Err(OrchestratorError::lock_poisoned("galaxy topology"))
         }
     }
 
     pub(crate) fn initialize_planets_by_ids_list(
         &mut self,
         ids_list: Vec<u32>,
-    ) -> Result<(), String> {
-        let mut err = false;
+    ) -> Result<(), OrchestratorError> {
         for planet_id in ids_list {
             //TODO we need to initialize the other planets randomly or precisely
             match self.galaxy_lookup.get(&planet_id) {
-                None => {
-                    err = true;
-                    break;
-                }
-                Some((_, typ)) => {
-                    self.add_planet(planet_id, typ.clone())?;
+                None => return Err(OrchestratorError::UnknownPlanet { planet_id }),
+                Some(typ) => {
+                    self.add_planet(planet_id, *typ)?;
                 }
             };
         }
-        match err {
-            false => Ok(()),
-            true => Err("no planet type found".to_string()),
-        }
+        Ok(())
     }
 }
 
 //Game functions
 impl Orchestrator {
+    /// Marks the galaxy as changed
+    ///
+    /// Every function that modifies topology or statuses must call it,
+    /// otherwise the UI would consider the new snapshot equal to the previous one
+    pub(crate) fn bump_version(&mut self) {
+        self.state_version += 1;
+    }
+
+    pub(crate) fn advance_tick(&mut self) {
+        let live_planets = self
+            .planets_status
+            .read()
+            .unwrap()
+            .values()
+            .filter(|status| !status.is_dead())
+            .count();
+        self.current_tick += 1;
+        publish_tick(self.current_tick);
+        self.metrics.end_tick(live_planets, self.current_tick);
+    }
+
+    /// Journals the planets and links of a galaxy that has just been created
+    fn record_galaxy_loaded(&mut self) {
+        let planets = self.galaxy_lookup.iter().map(|(id, typ)| (*id, *typ)).collect();
+        let links = self.get_topology().read().map(|gtop| gtop.links()).unwrap_or_default();
+        self.record(JournalEvent::GalaxyLoaded { planets, links });
+    }
+
+    /// Appends an event to the journal, if there is one
+    pub(crate) fn record(&mut self, event: JournalEvent) {
+        let Some(journal) = self.journal.as_mut() else {
+            return;
+        };
+        //A journal that cannot be written must not stop the game
+        if let Err(_e) = journal.record(self.current_tick, event) {
+            log_event!(Channel::Warning, self.current_tick, None, "Journal", "{_e}");
+        }
+    }
+
+    pub(crate) fn set_planet_status(&mut self, planet_id: u32, status: Status) {
+        let previous = self.planets_status.write().unwrap().insert(planet_id, status.clone());
+        if previous == Some(status.clone()) {
+            return;
+        }
+        self.bump_version();
+        self.record(JournalEvent::PlanetStatus { planet_id, status: status.clone() });
+        if status.is_dead() {
+            self.pending_acks.remove(&planet_id);
+            if !previous.as_ref().is_some_and(|previous| previous.is_dead()) {
+                self.planet_death_ticks.insert(planet_id, self.current_tick);
+                self.metrics.planet_destroyed(planet_id);
+            }
+        } else {
+            self.planet_death_ticks.remove(&planet_id);
+        }
+        //Only a planet that dies (or comes back) changes the graph of the live planets
+        if !status.is_dead() && !previous.is_some_and(|previous| previous.is_dead()) {
+            return;
+        }
+        if let Err(_e) = self.refresh_topology_analysis() {
+            log_event!(Channel::Error, self.current_tick, None, "TopologyAnalysis", "unable to refresh the topology analysis: {_e}");
+        }
+    }
+
+    pub(crate) fn set_explorer_status(&mut self, explorer_id: u32, status: Status) {
+        let previous = self.explorer_status.write().unwrap().insert(explorer_id, status.clone());
+        if previous != Some(status.clone()) {
+            self.bump_version();
+            self.record(JournalEvent::ExplorerStatus { explorer_id, status });
+        }
+    }
+
     /// Removes the link between two planets if one of them explodes.
     /// ``
-    /// Returns Err if the given indexes are out of bounds, Ok otherwise;
-    /// it does NOT currently check wether the link was already set to false beforehand
+    /// Returns Err if one of the planets is not part of the galaxy, Ok otherwise;
+    /// removing a link that does not exist does nothing
     ///
-    /// * `planet_one_pos` - Position of the first planet in the matrix. Must be a valid index
-    /// * `planet_two_pos` - Position of the second planet in the matrix. Must be a valid index
+    /// * `planet_one` - Id of the first planet
+    /// * `planet_two` - Id of the second planet
     pub(crate) fn destroy_topology_link(
         &mut self,
-        planet_one_pos: usize,
-        planet_two_pos: usize,
-    ) -> Result<(), String> {
-        match self.galaxy_topology.write() {
+        planet_one: u32,
+        planet_two: u32,
+    ) -> Result<(), OrchestratorError> {
+        let removed = match self.galaxy_topology.write() {
             Ok(mut gtop) => {
-                if planet_one_pos < gtop.len() && planet_two_pos < gtop.len() {
-                    gtop[planet_one_pos][planet_two_pos] = false;
-                    gtop[planet_two_pos][planet_one_pos] = false;
-                    drop(gtop);
-                    Ok(())
-                } else {
-                    Err("index out of bounds (too large)".to_string())
+                if let Some(planet_id) = [planet_one, planet_two].into_iter().find(|id| !gtop.contains(*id)) {
+                    return Err(OrchestratorError::UnknownPlanet { planet_id });
                 }
+                gtop.remove_link(planet_one, planet_two)
             }
-            Err(e) => {
-                debug_println!("RwLock failed for destroy_topology_link");
-                Err(e.to_string())
+            Err(_) => {
+                log_event!(Channel::Error, self.current_tick, None, "LockPoisoned", "galaxy topology, while destroying a link");
+                return Err(OrchestratorError::lock_poisoned("galaxy topology"));
             }
+        };
+        //A link that did not exist changes nothing, the UI gets no new frame
+        if !removed {
+            return Ok(());
         }
+        self.destroyed_links
+            .insert((planet_one.min(planet_two), planet_one.max(planet_two)));
+        self.record(JournalEvent::LinkDestroyed { planet_one, planet_two });
+        self.refresh_topology_analysis()
     }
 
-    pub(crate) fn start_all_planet_ais(&mut self) -> Result<(), String> {
-        for (_id, (from_orch, _)) in &self.planet_channels {
-            from_orch
+    /// Starts every planet and waits for their answers until the ack deadline
+    ///
+    /// Planets that do not answer in time are marked as unresponsive, the game goes on without them
+    pub(crate) fn start_all_planet_ais(&mut self) -> Result<(), OrchestratorError> {
+        let planet_ids: Vec<u32> = self.planet_channels.keys().copied().collect();
+        for id in planet_ids {
+            self.planet_channels[&id]
+                .0
                 .try_send(OrchestratorToPlanet::StartPlanetAI)
-                .map_err(|_| "Cannot send message to {_id}".to_string())?;
+                .map_err(|_| OrchestratorError::disconnected(Actor::Planet(id)))?;
+            self.expect_ack(id);
         }
 
+        let deadline = Instant::now() + settings::get_planet_ack_timeout();
         let mut count = 0;
-        //REVIEW is it possible that this loop could block forevere the game?
-        loop {
-            if count == self.planet_channels.len() {
-                break;
-            }
-            let receive_channel = self
-                .recevier_orch_planet
-                .recv()
-                .map_err(|_| "Cannot receive message from planets".to_string())?;
+        while count < self.planet_channels.len() {
+            let receive_channel = match self.recevier_orch_planet.recv_deadline(deadline) {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(OrchestratorError::disconnected(Actor::AllPlanets));
+                }
+            };
             match receive_channel {
                 PlanetToOrchestrator::StartPlanetAIResult { planet_id } => {
-                    debug_println!("Started Planet AI: {}", planet_id);
-                    self.planets_status.write().unwrap().insert(planet_id, Status::Running);
+                    log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Planet(planet_id)), "StartPlanetAIResult", "planet AI started");
+                    self.ack_received(planet_id);
+                    self.set_planet_status(planet_id, Status::Running);
                     count += 1;
                 }
                 _ => {}
             }
         }
+        //A planet that crashed while starting is not just late
+        self.check_planet_threads()?;
+        self.check_planet_deadlines(deadline)
+    }
+
+    /// Remembers that the planet must acknowledge a request within the ack timeout
+    fn expect_ack(&mut self, planet_id: u32) {
+        let now = Instant::now();
+        self.pending_acks
+            .entry(planet_id)
+            .or_default()
+            .push_back(now + settings::get_planet_ack_timeout());
+        self.metrics.request_sent(planet_id, now);
+    }
+
+    /// Planets answer in order, so an ack always matches the oldest pending request
+    ///
+    /// An unresponsive planet that answers is running again
+    fn ack_received(&mut self, planet_id: u32) {
+        self.metrics.ack_received(planet_id, Instant::now());
+        if let Some(deadlines) = self.pending_acks.get_mut(&planet_id) {
+            deadlines.pop_front();
+            if deadlines.is_empty() {
+                self.pending_acks.remove(&planet_id);
+            }
+        }
+        if self.planets_status.read().unwrap().get(&planet_id) == Some(&Status::Unresponsive) {
+            self.set_planet_status(planet_id, Status::Running);
+        }
+    }
+
+    /// Marks as unresponsive every planet whose oldest request is older than its deadline at `now`
+    ///
+    /// With `kill_unresponsive_planets` the planet is also killed and its links are destroyed.
+    /// Every planet is reported once to the UI with `GameToUi::PlanetUnresponsive`
+    pub(crate) fn check_planet_deadlines(&mut self, now: Instant) -> Result<(), OrchestratorError> {
+        //Acks already received are not late, e.g. the ones that arrived while the game was paused
+        while let Ok(msg) = self.recevier_orch_planet.try_recv() {
+            self.handle_planet_message(msg)?;
+        }
+        let overdue: Vec<u32> = self
+            .pending_acks
+            .iter()
+            .filter(|(_, deadlines)| deadlines.front().is_some_and(|deadline| *deadline <= now))
+            .map(|(planet_id, _)| *planet_id)
+            .filter(|planet_id| {
+                matches!(
+                    self.planets_status.read().unwrap().get(planet_id),
+                    Some(Status::Running | Status::Paused)
+                )
+            })
+            .collect();
+
+        for planet_id in overdue {
+            log_event!(Channel::Warning, self.current_tick, Some(Actor::Planet(planet_id)), "PlanetUnresponsive", "no ack before the deadline");
+            self.set_planet_status(planet_id, Status::Unresponsive);
+            if self.kill_unresponsive_planets {
+                self.isolate_planet(planet_id)?;
+            }
+            self.ui_events.push_back(GameToUi::PlanetUnresponsive {
+                planet_id,
+                killed: self.kill_unresponsive_planets,
+            });
+        }
+        Ok(())
+    }
+
+    /// Kills a planet and destroys all its links, the planet stays in the galaxy as a dead one
+    fn isolate_planet(&mut self, planet_id: u32) -> Result<(), OrchestratorError> {
+        //A hung planet may never read the kill message, it is dead for the game anyway
+        if let Some((sender, _)) = self.planet_channels.get(&planet_id) {
+            let _ = self.send_planet_kill(sender);
+        }
+        let neighbors = self
+            .galaxy_topology
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?
+            .neighbors(planet_id);
+        for neighbor in neighbors {
+            self.destroy_topology_link(planet_id, neighbor)?;
+        }
+        self.set_planet_status(planet_id, Status::Dead);
         Ok(())
     }
 
     pub(crate) fn handle_planet_message(
         &mut self,
         msg: PlanetToOrchestrator,
-    ) -> Result<(), String> {
+    ) -> Result<(), OrchestratorError> {
         match msg {
             PlanetToOrchestrator::SunrayAck { planet_id } => {
-                debug_println!("SunrayAck from: {}", planet_id)
+                log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Planet(planet_id)), "SunrayAck", "");
+                self.record(JournalEvent::SunrayAck { planet_id });
+                self.ack_received(planet_id);
             }
             PlanetToOrchestrator::AsteroidAck { planet_id, rocket } => {
-                debug_println!("AsteroidAck from: {}", planet_id);
+                //The planet may have been removed, crashed or killed after the asteroid was sent: its ack is late, not an error
+                let Some(sender) = self
+                    .planet_channels
+                    .get(&planet_id)
+                    .filter(|_| self.is_planet_alive(planet_id))
+                    .map(|(sender, _)| sender.clone())
+                else {
+                    log_event!(Channel::Warning, self.current_tick, Some(Actor::Planet(planet_id)), "AsteroidAck", "late ack from a planet no longer alive, dropped");
+                    return Ok(());
+                };
+                log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Planet(planet_id)), "AsteroidAck", "rocket: {}", rocket.is_some());
+                self.record(JournalEvent::AsteroidAck { planet_id, rocket: rocket.is_some() });
+                self.ack_received(planet_id);
                 match rocket {
-                    Some(_) => {
-                        //TODO some logging function
-                    }
+                    Some(_) => self.metrics.rocket_fired(),
                     None => {
-                        //If you have the id then surely that planet exist so we can unwrap without worring
-                        let sender = &self.planet_channels.get(&planet_id).unwrap().0;
-                        sender
-                            .send(OrchestratorToPlanet::KillPlanet)
-                            .map_err(|_| "Unable to send to planet: {planet_id}")?;
+                        //A thread that has just ended is found by check_planet_threads, the planet is dead anyway
+                        if sender.send(OrchestratorToPlanet::KillPlanet).is_err() {
+                            log_event!(Channel::Warning, self.current_tick, Some(Actor::Planet(planet_id)), "KillPlanet", "the planet thread has already ended");
+                        }
 
                         //Update planet State
-                        self.planets_status.write().unwrap().insert(planet_id, Status::Dead);
+                        self.set_planet_status(planet_id, Status::Dead);
                         //TODO we need to do a check if some explorer is on that planet
                     }
                 }
@@ -531,12 +786,21 @@ impl Orchestrator {
             PlanetToOrchestrator::InternalStateResponse {
                 planet_id,
                 planet_state,
-            } => {}
+            } => {
+                self.ack_received(planet_id);
+                self.planet_states.insert(planet_id, PlanetStateSummary::from(&planet_state));
+            }
             PlanetToOrchestrator::KillPlanetResult { planet_id } => {
-                debug_println!("Planet killed: {}", planet_id);
+                log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Planet(planet_id)), "KillPlanetResult", "planet killed");
             }
             // PlanetToOrchestrator::OutgoingExplorerResponse { planet_id, res }=>{},
-            PlanetToOrchestrator::StartPlanetAIResult { planet_id } => {}
+            PlanetToOrchestrator::StartPlanetAIResult { planet_id } => {
+                //Planets started one by one while the game is running, the others are handled by start_all_planet_ais
+                self.ack_received(planet_id);
+                if self.is_planet_alive(planet_id) {
+                    self.set_planet_status(planet_id, Status::Running);
+                }
+            }
             PlanetToOrchestrator::StopPlanetAIResult { planet_id } => {}
             PlanetToOrchestrator::Stopped { planet_id } => {}
             _ => {}
@@ -544,16 +808,31 @@ impl Orchestrator {
         Ok(())
     }
 
-    pub(crate) fn send_sunray(&self, sender: &Sender<OrchestratorToPlanet>) -> Result<(), String> {
+    /// Planet a sender belongs to, used to report which channel is closed
+    fn channel_owner(&self, sender: &Sender<OrchestratorToPlanet>) -> Actor {
+        self.planet_channels
+            .iter()
+            .find(|(_, (planet_sender, _))| planet_sender.same_channel(sender))
+            .map(|(planet_id, _)| Actor::Planet(*planet_id))
+            .unwrap_or(Actor::AllPlanets)
+    }
+
+    pub(crate) fn send_sunray(&self, sender: &Sender<OrchestratorToPlanet>) -> Result<(), OrchestratorError> {
+        log_event!(LOG_FN_INT_OPERATIONS, self.current_tick, Some(self.channel_owner(sender)), "Sunray", "");
         sender
             .send(OrchestratorToPlanet::Sunray(self.forge.generate_sunray()))
-            .map_err(|_| "Unable to send a sunray to planet: {id}".to_string())
+            .map_err(|_| OrchestratorError::disconnected(self.channel_owner(sender)))
     }
-    pub(crate) fn send_sunray_to_all(&self) -> Result<(), String> {
-        for (id, (sender, _)) in &self.planet_channels {
-            if *self.planets_status.read().unwrap().get(id).unwrap() != Status::Dead {
-                self.send_sunray(sender)?;
+    pub(crate) fn send_sunray_to_all(&mut self) -> Result<(), OrchestratorError> {
+        for id in self.responsive_planets() {
+            //A closed channel means that the planet thread ended, the other planets still get their sunray
+            if self.send_sunray(&self.planet_channels[&id].0).is_err() {
+                self.planet_thread_ended(id)?;
+                continue;
             }
+            self.record(JournalEvent::SunraySent { planet_id: id });
+            self.metrics.sunray_sent();
+            self.expect_ack(id);
         }
         Ok(())
     }
@@ -561,32 +840,84 @@ impl Orchestrator {
     pub(crate) fn send_asteroid(
         &self,
         sender: &Sender<OrchestratorToPlanet>,
-    ) -> Result<(), String> {
+    ) -> Result<(), OrchestratorError> {
+        log_event!(LOG_FN_INT_OPERATIONS, self.current_tick, Some(self.channel_owner(sender)), "Asteroid", "");
         sender
             .send(OrchestratorToPlanet::Asteroid(
                 self.forge.generate_asteroid(),
             ))
-            .map_err(|_| "Unable to send sunray to planet: {id}".to_string())
+            .map_err(|_| OrchestratorError::disconnected(self.channel_owner(sender)))
     }
-    pub(crate) fn send_asteroid_to_all(&self) -> Result<(), String> {
-        //unwrap cannot fail because every id is contained in the map
-        for (id, (sender, _)) in &self.planet_channels {
-            if *self.planets_status.read().unwrap().get(id).unwrap() != Status::Dead {
-                self.send_asteroid(sender)?;
+    pub(crate) fn send_asteroid_to_all(&mut self) -> Result<(), OrchestratorError> {
+        for id in self.responsive_planets() {
+            if self.send_asteroid(&self.planet_channels[&id].0).is_err() {
+                self.planet_thread_ended(id)?;
+                continue;
             }
+            self.record(JournalEvent::AsteroidSent { planet_id: id });
+            self.metrics.asteroid_sent();
+            self.expect_ack(id);
+        }
+        Ok(())
+    }
+
+    /// Finds the planets whose thread has ended, see `planet_thread_ended`
+    pub(crate) fn check_planet_threads(&mut self) -> Result<(), OrchestratorError> {
+        let ended: Vec<u32> = self
+            .planet_threads
+            .iter()
+            .filter(|(_, handle)| handle.is_finished())
+            .map(|(planet_id, _)| *planet_id)
+            .collect();
+        for planet_id in ended {
+            self.planet_thread_ended(planet_id)?;
+        }
+        Ok(())
+    }
+
+    /// Joins the thread of a planet that stopped or whose channel is closed
+    ///
+    /// A killed planet just ends. Any other planet crashed: it is marked as `Status::Crashed`,
+    /// its channels are dropped so it leaves the live topology, and the UI gets `GameToUi::PlanetCrashed`
+    pub(crate) fn planet_thread_ended(&mut self, planet_id: u32) -> Result<(), OrchestratorError> {
+        let reason = match self.planet_threads.remove(&planet_id).map(JoinHandle::join) {
+            Some(Ok(Ok(()))) => "the planet stopped without being killed".to_string(),
+            Some(Ok(Err(e))) => e,
+            Some(Err(payload)) => format!("panicked: {}", panic_message(payload)),
+            None => "the planet channel is closed".to_string(),
+        };
+        let status = self.planets_status.read().unwrap().get(&planet_id).cloned();
+        if status.is_none_or(|status| status.is_dead()) {
+            return Ok(());
         }
+
+        log_event!(Channel::Error, self.current_tick, Some(Actor::Planet(planet_id)), "PlanetCrashed", "{reason}");
+        self.planet_channels.remove(&planet_id);
+        self.planet_crashes.insert(planet_id, reason.clone());
+        self.set_planet_status(planet_id, Status::Crashed);
+        self.ui_events.push_back(GameToUi::PlanetCrashed { planet_id, reason });
         Ok(())
     }
 
+    /// Planets that are alive and answering, unresponsive ones get nothing new until they catch up
+    fn responsive_planets(&self) -> Vec<u32> {
+        let planets_status = self.planets_status.read().unwrap();
+        self.planet_channels
+            .keys()
+            .filter(|id| matches!(planets_status.get(*id), Some(Status::Running | Status::Paused)))
+            .copied()
+            .collect()
+    }
+
     pub(crate) fn send_planet_kill(
         &self,
         sender: &Sender<OrchestratorToPlanet>,
-    ) -> Result<(), String> {
+    ) -> Result<(), OrchestratorError> {
         sender
             .send(OrchestratorToPlanet::KillPlanet)
-            .map_err(|_| "Unable to send kill message to planet: {id}".to_string())
+            .map_err(|_| OrchestratorError::disconnected(self.channel_owner(sender)))
     }
-    pub(crate) fn send_planet_kill_to_all(&self) -> Result<(), String> {
+    pub(crate) fn send_planet_kill_to_all(&self) -> Result<(), OrchestratorError> {
         for (id, (sender, _)) in &self.planet_channels {
             //unwrap cannot fail because every id is contained in the map
             if *self.planets_status.read().unwrap().get(id).unwrap() != Status::Dead {
@@ -597,17 +928,21 @@ impl Orchestrator {
     }
 
     /// Run by the game loop, it should handle the messages from planets and explorers
-    pub(crate) fn handle_game_messages(&mut self) -> Result<(), String> {
+    pub(crate) fn handle_game_messages(&mut self) -> Result<(), OrchestratorError> {
         select! {
             recv(self.recevier_orch_planet)->msg=>{
                 let msg_unwraped = match msg{
                     Ok(res)=>res,
-                    Err(_)=>return Err("Cannot receive message from planets".to_string()),
+                    Err(_)=>return Err(OrchestratorError::disconnected(Actor::AllPlanets)),
                 };
                 self.handle_planet_message(msg_unwraped)?;
             }
             recv(self.receiver_orch_explorer)->msg=>{
-                todo!()
+                let msg_unwraped = match msg{
+                    Ok(res)=>res,
+                    Err(_)=>return Err(OrchestratorError::disconnected(Actor::AllExplorers)),
+                };
+                self.handle_explorer_message(msg_unwraped)?;
             }
             default=>{}
         }
@@ -615,21 +950,710 @@ impl Orchestrator {
         Ok(())
     }
 }
+//Manual interaction functions, called by the game on UI commands
+impl Orchestrator {
+    pub(crate) fn is_planet_alive(&self, planet_id: u32) -> bool {
+        matches!(
+            self.planets_status.read().unwrap().get(&planet_id),
+            Some(status) if !status.is_dead()
+        )
+    }
+
+    pub(crate) fn is_explorer_alive(&self, explorer_id: u32) -> bool {
+        matches!(
+            self.explorer_status.read().unwrap().get(&explorer_id),
+            Some(status) if *status != Status::Dead
+        )
+    }
+
+    /// Returns the orchestrator sender of a planet that is still alive
+    fn alive_planet_sender(&self, planet_id: u32) -> Result<&Sender<OrchestratorToPlanet>, OrchestratorError> {
+        if !self.planets_status.read().unwrap().contains_key(&planet_id) {
+            return Err(OrchestratorError::UnknownPlanet { planet_id });
+        }
+        if !self.is_planet_alive(planet_id) {
+            return Err(OrchestratorError::PlanetNotAlive { planet_id });
+        }
+        self.planet_channels
+            .get(&planet_id)
+            .map(|(sender, _)| sender)
+            .ok_or(OrchestratorError::disconnected(Actor::Planet(planet_id)))
+    }
+
+    fn alive_explorer_sender(
+        &self,
+        explorer_id: u32,
+    ) -> Result<&Sender<OrchestratorToExplorer>, OrchestratorError> {
+        if !self.explorer_status.read().unwrap().contains_key(&explorer_id) {
+            return Err(OrchestratorError::UnknownExplorer { explorer_id });
+        }
+        if !self.is_explorer_alive(explorer_id) {
+            return Err(OrchestratorError::ExplorerNotAlive { explorer_id });
+        }
+        self.explorer_channels
+            .get(&explorer_id)
+            .map(|(sender, _)| sender)
+            .ok_or(OrchestratorError::disconnected(Actor::Explorer(explorer_id)))
+    }
+
+    pub(crate) fn send_sunray_to(&mut self, planet_id: u32) -> Result<(), OrchestratorError> {
+        let sender = self.alive_planet_sender(planet_id)?;
+        if let Err(e) = self.send_sunray(sender) {
+            self.planet_thread_ended(planet_id)?;
+            return Err(e);
+        }
+        self.record(JournalEvent::SunraySent { planet_id });
+        self.metrics.sunray_sent();
+        self.expect_ack(planet_id);
+        Ok(())
+    }
+
+    pub(crate) fn send_asteroid_to(&mut self, planet_id: u32) -> Result<(), OrchestratorError> {
+        let sender = self.alive_planet_sender(planet_id)?;
+        if let Err(e) = self.send_asteroid(sender) {
+            self.planet_thread_ended(planet_id)?;
+            return Err(e);
+        }
+        self.record(JournalEvent::AsteroidSent { planet_id });
+        self.metrics.asteroid_sent();
+        self.expect_ack(planet_id);
+        Ok(())
+    }
+
+    /// Creates a new explorer on the given planet and tells the planet it has arrived
+    pub(crate) fn spawn_explorer(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), OrchestratorError> {
+        if self.explorer_status.read().unwrap().contains_key(&explorer_id) {
+            return Err(OrchestratorError::invalid(format!("Explorer {explorer_id} already exists")));
+        }
+        let sender_explorer = self
+            .planet_channels
+            .get(&planet_id)
+            .map(|(_, sender_explorer)| sender_explorer.clone())
+            .ok_or(OrchestratorError::UnknownPlanet { planet_id })?;
+        let planet_sender = self.alive_planet_sender(planet_id)?.clone();
+
+        //The energy cells of the planet are unknown until the explorer asks for them
+        self.add_explorer(explorer_id, planet_id, 0, sender_explorer);
+
+        let (_, sender_to_explorer) = self.explorer_channels.get(&explorer_id).unwrap();
+        planet_sender
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id,
+                new_sender: sender_to_explorer.clone(),
+            })
+            .map_err(|_| OrchestratorError::disconnected(Actor::Planet(planet_id)))
+    }
+
+    pub(crate) fn start_explorer_ai(&self, explorer_id: u32) -> Result<(), OrchestratorError> {
+        self.alive_explorer_sender(explorer_id)?
+            .send(OrchestratorToExplorer::StartExplorerAI)
+            .map_err(|_| OrchestratorError::disconnected(Actor::Explorer(explorer_id)))
+    }
+
+    pub(crate) fn start_all_explorer_ais(&self) -> Result<(), OrchestratorError> {
+        let explorer_ids: Vec<u32> = self.explorer_channels.keys().copied().collect();
+        for explorer_id in explorer_ids {
+            if self.is_explorer_alive(explorer_id) {
+                self.start_explorer_ai(explorer_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn kill_explorer(&mut self, explorer_id: u32) -> Result<(), OrchestratorError> {
+        self.alive_explorer_sender(explorer_id)?
+            .send(OrchestratorToExplorer::KillExplorer)
+            .map_err(|_| OrchestratorError::disconnected(Actor::Explorer(explorer_id)))?;
+
+        //The planet must stop answering to the dead explorer
+        if let Some(planet_id) = self.explorer_positions.get(&explorer_id).copied()
+            && let Ok(sender) = self.alive_planet_sender(planet_id)
+        {
+            sender
+                .send(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id })
+                .map_err(|_| OrchestratorError::disconnected(Actor::Planet(planet_id)))?;
+        }
+        self.set_explorer_status(explorer_id, Status::Dead);
+        Ok(())
+    }
+
+    /// Moves an explorer to a planet directly connected to the one it is on
+    pub(crate) fn move_explorer(&mut self, explorer_id: u32, planet_id: u32) -> Result<(), OrchestratorError> {
+        let explorer_sender = self.alive_explorer_sender(explorer_id)?.clone();
+        let current_planet = *self
+            .explorer_positions
+            .get(&explorer_id)
+            .ok_or(OrchestratorError::UnknownExplorer { explorer_id })?;
+        if current_planet == planet_id {
+            return Err(OrchestratorError::invalid(format!("Explorer {explorer_id} is already on planet {planet_id}")));
+        }
+
+        let gtop = self
+            .galaxy_topology
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?;
+        if !gtop.contains(planet_id) {
+            return Err(OrchestratorError::UnknownPlanet { planet_id });
+        }
+        let connected = gtop.are_linked(current_planet, planet_id);
+        drop(gtop);
+        if !connected {
+            return Err(OrchestratorError::invalid(format!(
+                "Planet {current_planet} and planet {planet_id} are not connected"
+            )));
+        }
+
+        let new_planet_sender = self.alive_planet_sender(planet_id)?.clone();
+        let (_, sender_explorer) = self.planet_channels.get(&planet_id).unwrap();
+        let sender_explorer = sender_explorer.clone();
+        let (_, sender_to_explorer) = self.explorer_channels.get(&explorer_id).unwrap();
+
+        //First the new planet is connected and only after the old one is disconnected
+        new_planet_sender
+            .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                explorer_id,
+                new_sender: sender_to_explorer.clone(),
+            })
+            .map_err(|_| OrchestratorError::disconnected(Actor::Planet(planet_id)))?;
+        if let Ok(old_planet_sender) = self.alive_planet_sender(current_planet) {
+            old_planet_sender
+                .send(OrchestratorToPlanet::OutgoingExplorerRequest { explorer_id })
+                .map_err(|_| OrchestratorError::disconnected(Actor::Planet(current_planet)))?;
+        }
+        explorer_sender
+            .send(OrchestratorToExplorer::MoveToPlanet {
+                sender_to_new_planet: Some(sender_explorer),
+            })
+            .map_err(|_| OrchestratorError::disconnected(Actor::Explorer(explorer_id)))?;
+
+        self.explorer_positions.insert(explorer_id, planet_id);
+        self.bump_version();
+        self.record(JournalEvent::ExplorerMoved { explorer_id, planet_id });
+        Ok(())
+    }
+
+    /// Adds a new planet to the galaxy, connected to the given neighbors
+    ///
+    /// The new planet is created paused like the ones of the init file,
+    /// the UI is told about it with `GameToUi::PlanetAdded`
+    pub(crate) fn add_planet_with_links(
+        &mut self,
+        planet_id: u32,
+        planet_type: PlanetType,
+        neighbors: &[u32],
+    ) -> Result<(), OrchestratorError> {
+        if self.planets_status.read().unwrap().contains_key(&planet_id) {
+            return Err(OrchestratorError::invalid(format!("Planet {planet_id} already exists")));
+        }
+        for neighbor in neighbors {
+            if !self.planets_status.read().unwrap().contains_key(neighbor) {
+                return Err(OrchestratorError::UnknownPlanet { planet_id: *neighbor });
+            }
+        }
+
+        //The planet is created first, so that a failing factory leaves the topology untouched
+        self.add_planet(planet_id, planet_type)?;
+
+        let mut gtop = self
+            .galaxy_topology
+            .write()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?;
+        gtop.add_planet(planet_id);
+        for neighbor in neighbors {
+            //Planets created without being part of the topology (e.g. in the tests) join it now
+            gtop.add_planet(*neighbor);
+            gtop.add_link(planet_id, *neighbor).map_err(OrchestratorError::invalid)?;
+        }
+        drop(gtop);
+
+        self.galaxy_lookup.insert(planet_id, planet_type);
+        self.record(JournalEvent::PlanetAdded {
+            planet_id,
+            planet_type,
+            neighbors: neighbors.to_vec(),
+        });
+        self.ui_events.push_back(GameToUi::PlanetAdded {
+            planet_id,
+            planet_type,
+            neighbors: neighbors.to_vec(),
+        });
+        self.refresh_topology_analysis()
+    }
+
+    /// Starts the AI of a single planet, e.g. one added while the game is running
+    ///
+    /// The planet is marked as running when it answers
+    pub(crate) fn start_planet_ai(&mut self, planet_id: u32) -> Result<(), OrchestratorError> {
+        self.alive_planet_sender(planet_id)?
+            .send(OrchestratorToPlanet::StartPlanetAI)
+            .map_err(|_| OrchestratorError::disconnected(Actor::Planet(planet_id)))?;
+        self.expect_ack(planet_id);
+        Ok(())
+    }
+
+    /// Removes a planet from the galaxy together with its links
+    ///
+    /// The planet is killed if it is still alive. Explorers are not moved
+    /// automatically: the removal fails while some explorer is on the planet
+    pub(crate) fn remove_planet(&mut self, planet_id: u32) -> Result<(), OrchestratorError> {
+        let mut gtop = self
+            .galaxy_topology
+            .write()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?;
+        if !gtop.contains(planet_id) && !self.planet_channels.contains_key(&planet_id) {
+            return Err(OrchestratorError::UnknownPlanet { planet_id });
+        }
+        let guests: Vec<u32> = self
+            .explorer_positions
+            .iter()
+            .filter(|(explorer_id, position)| **position == planet_id && self.is_explorer_alive(**explorer_id))
+            .map(|(explorer_id, _)| *explorer_id)
+            .collect();
+        if !guests.is_empty() {
+            return Err(OrchestratorError::invalid(format!(
+                "Planet {planet_id} still hosts the explorers {guests:?}, move them first"
+            )));
+        }
+        gtop.remove_planet(planet_id);
+        drop(gtop);
+
+        if let Ok(sender) = self.alive_planet_sender(planet_id) {
+            self.send_planet_kill(sender)?;
+        }
+        self.planet_channels.remove(&planet_id);
+        self.planets_status.write().unwrap().remove(&planet_id);
+        self.galaxy_lookup.remove(&planet_id);
+        self.planet_configs.remove(&planet_id);
+        self.pending_acks.remove(&planet_id);
+        self.planet_threads.remove(&planet_id);
+        self.planet_crashes.remove(&planet_id);
+        self.planet_states.remove(&planet_id);
+        self.pending_recharges.remove(&planet_id);
+        self.planet_birth_ticks.remove(&planet_id);
+        self.planet_death_ticks.remove(&planet_id);
+        self.destroyed_links
+            .retain(|(one, two)| *one != planet_id && *two != planet_id);
+
+        self.record(JournalEvent::PlanetRemoved { planet_id });
+        self.ui_events.push_back(GameToUi::PlanetRemoved { planet_id });
+        self.refresh_topology_analysis()
+    }
+
+    pub(crate) fn remove_link(&mut self, planet_one: u32, planet_two: u32) -> Result<(), OrchestratorError> {
+        self.destroy_topology_link(planet_one, planet_two)
+    }
+
+    /// Asks the explorer for its bag, the content is sent to the UI when the explorer answers
+    pub(crate) fn request_bag_content(&mut self, explorer_id: u32) -> Result<(), OrchestratorError> {
+        self.alive_explorer_sender(explorer_id)?
+            .send(OrchestratorToExplorer::BagContentRequest)
+            .map_err(|_| OrchestratorError::disconnected(Actor::Explorer(explorer_id)))?;
+        self.ui_bag_requests.insert(explorer_id);
+        Ok(())
+    }
+
+    /// Asks every alive explorer for its bag without telling the UI, the answers update `explorer_bags`
+    ///
+    /// Returns the explorers that were asked
+    pub(crate) fn request_explorer_bags(&self) -> BTreeSet<u32> {
+        self.explorer_channels
+            .keys()
+            .copied()
+            .filter(|explorer_id| {
+                self.alive_explorer_sender(*explorer_id)
+                    .is_ok_and(|sender| sender.send(OrchestratorToExplorer::BagContentRequest).is_ok())
+            })
+            .collect()
+    }
+
+    pub(crate) fn handle_explorer_message(
+        &mut self,
+        msg: ExplorerToOrchestrator<BagType>,
+    ) -> Result<(), OrchestratorError> {
+        //Answers to the lifecycle commands and to the bag polls of the orchestrator are not actions of the explorer
+        let is_action = match &msg {
+            ExplorerToOrchestrator::StartExplorerAIResult { .. }
+            | ExplorerToOrchestrator::StopExplorerAIResult { .. }
+            | ExplorerToOrchestrator::ResetExplorerAIResult { .. }
+            | ExplorerToOrchestrator::KillExplorerResult { .. } => false,
+            ExplorerToOrchestrator::BagContentResponse { explorer_id, .. } => self.ui_bag_requests.contains(explorer_id),
+            _ => true,
+        };
+        if is_action {
+            self.metrics.explorer_action();
+        }
+        match msg {
+            ExplorerToOrchestrator::StartExplorerAIResult { explorer_id } => {
+                log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Explorer(explorer_id)), "StartExplorerAIResult", "explorer AI started");
+                self.set_explorer_status(explorer_id, Status::Running);
+            }
+            ExplorerToOrchestrator::StopExplorerAIResult { explorer_id } => {
+                self.set_explorer_status(explorer_id, Status::Paused);
+            }
+            ExplorerToOrchestrator::KillExplorerResult { explorer_id } => {
+                log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, Some(Actor::Explorer(explorer_id)), "KillExplorerResult", "explorer killed");
+                self.set_explorer_status(explorer_id, Status::Dead);
+            }
+            ExplorerToOrchestrator::BagContentResponse {
+                explorer_id,
+                bag_content,
+            } => {
+                self.record(JournalEvent::ExplorerBag {
+                    explorer_id,
+                    bag: bag_content.clone(),
+                });
+                self.explorer_bags.insert(explorer_id, bag_content.clone());
+                if self.ui_bag_requests.remove(&explorer_id) {
+                    self.ui_events.push_back(GameToUi::CommandResult(Ok(CommandReply::BagContent {
+                        explorer_id,
+                        bag: bag_content,
+                    })));
+                }
+            }
+            ExplorerToOrchestrator::NeighborsRequest { explorer_id, .. } => {
+                let neighbors = match self.explorer_positions.get(&explorer_id) {
+                    Some(planet_id) => self
+                        .galaxy_topology
+                        .read()
+                        .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?
+                        .neighbors(*planet_id),
+                    None => Vec::new(),
+                };
+                self.record(JournalEvent::ExplorerNeighbors {
+                    explorer_id,
+                    neighbors: neighbors.clone(),
+                });
+                if let Ok(sender) = self.alive_explorer_sender(explorer_id) {
+                    sender
+                        .send(OrchestratorToExplorer::NeighborsResponse { neighbors })
+                        .map_err(|_| OrchestratorError::disconnected(Actor::Explorer(explorer_id)))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
 //Functions used by the game
 impl Orchestrator {
-    pub(crate) fn start_all(&mut self) -> Result<(), String> {
+    pub(crate) fn start_all(&mut self) -> Result<(), OrchestratorError> {
         self.start_all_planet_ais()?;
+        self.recharge_restored_planets()?;
+        self.start_all_explorer_ais()?;
         Ok(())
     }
-    pub(crate) fn stop_all(&mut self) -> Result<(), String> {
+    pub(crate) fn stop_all(&mut self) -> Result<(), OrchestratorError> {
         todo!();
         Ok(())
     }
 }
 
+//Checkpoints
+impl Orchestrator {
+    /// Current state of the galaxy, with the last planet states and bags received
+    ///
+    /// Use `save_checkpoint` to ask the planets and explorers for fresh ones first
+    pub(crate) fn checkpoint(&self) -> Result<GalaxyCheckpoint, OrchestratorError> {
+        let links = self
+            .galaxy_topology
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?
+            .links();
+        let planets = self
+            .planets_status
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("planets status"))?
+            .iter()
+            .filter_map(|(planet_id, status)| {
+                //Planets created without a type (e.g. in the tests) cannot be created again
+                let planet_type = *self.galaxy_lookup.get(planet_id)?;
+                Some((
+                    *planet_id,
+                    PlanetCheckpoint {
+                        planet_type,
+                        status: status.clone(),
+                        config: self.planet_configs.get(planet_id).cloned().unwrap_or_default(),
+                        internal_state: self.planet_states.get(planet_id).cloned(),
+                    },
+                ))
+            })
+            .collect();
+        let explorers = self
+            .explorer_status
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("explorers status"))?
+            .iter()
+            .filter_map(|(explorer_id, status)| {
+                Some((
+                    *explorer_id,
+                    ExplorerCheckpoint {
+                        status: status.clone(),
+                        planet_id: *self.explorer_positions.get(explorer_id)?,
+                        //Dead explorers never answer again, their bag is the restored one
+                        bag: self
+                            .explorer_bags
+                            .get(explorer_id)
+                            .or_else(|| self.restored_bags.get(explorer_id))
+                            .cloned()
+                            .unwrap_or_default(),
+                    },
+                ))
+            })
+            .collect();
+        Ok(GalaxyCheckpoint {
+            tick: self.current_tick,
+            planets,
+            links,
+            destroyed_links: self.destroyed_links.iter().copied().collect(),
+            explorers,
+            sunray_asteroid_sequence: settings::get_sunray_asteroid_sequence(),
+        })
+    }
+
+    /// Writes a checkpoint of the galaxy to `path`
+    ///
+    /// Planets and explorers are asked for their internal state and bag, the ones that
+    /// do not answer before the ack deadline are saved with the last answer received
+    pub(crate) fn save_checkpoint(&mut self, path: &str) -> Result<GalaxyCheckpoint, OrchestratorError> {
+        self.collect_planet_states()?;
+        self.collect_explorer_bags()?;
+        let checkpoint = self.checkpoint()?;
+        checkpoint
+            .save(path)
+            .map_err(|reason| OrchestratorError::Checkpoint { reason })?;
+        log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, None, "CheckpointSaved", "{path}");
+        Ok(checkpoint)
+    }
+
+    fn collect_planet_states(&mut self) -> Result<(), OrchestratorError> {
+        let deadline = Instant::now() + settings::get_planet_ack_timeout();
+
+        let mut missing_planets = BTreeSet::new();
+        for planet_id in self.responsive_planets() {
+            if self.planet_channels[&planet_id]
+                .0
+                .send(OrchestratorToPlanet::InternalStateRequest)
+                .is_ok()
+            {
+                self.expect_ack(planet_id);
+                missing_planets.insert(planet_id);
+            }
+        }
+
+        //Other messages arriving meanwhile are handled as usual
+        while !missing_planets.is_empty() {
+            let msg = match self.recevier_orch_planet.recv_deadline(deadline) {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(OrchestratorError::disconnected(Actor::AllPlanets));
+                }
+            };
+            if let PlanetToOrchestrator::InternalStateResponse { planet_id, .. } = &msg {
+                missing_planets.remove(planet_id);
+            }
+            self.handle_planet_message(msg)?;
+        }
+        if !missing_planets.is_empty() {
+            log_event!(Channel::Warning, self.current_tick, None, "Checkpoint", "no internal state from the planets {missing_planets:?}");
+        }
+        Ok(())
+    }
+
+    /// Asks every alive explorer for its bag and waits for the answers until the ack deadline
+    pub(crate) fn collect_explorer_bags(&mut self) -> Result<(), OrchestratorError> {
+        let deadline = Instant::now() + settings::get_planet_ack_timeout();
+        let mut missing_explorers = self.request_explorer_bags();
+        while !missing_explorers.is_empty() {
+            let msg = match self.receiver_orch_explorer.recv_deadline(deadline) {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(OrchestratorError::disconnected(Actor::AllExplorers));
+                }
+            };
+            //Bags are forwarded to the UI only if it asked for them too
+            if let ExplorerToOrchestrator::BagContentResponse { explorer_id, .. } = &msg {
+                missing_explorers.remove(explorer_id);
+            }
+            self.handle_explorer_message(msg)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the galaxy of a checkpoint, ready to be given to the game
+    ///
+    /// Planets are created again paused and dead planets stay dead. The energy cells
+    /// are charged again with sunrays once the game starts, rockets and bags cannot be restored:
+    /// the saved bags are kept in `restored_bags`, the scores only count the new ones
+    pub(crate) fn from_checkpoint(checkpoint: GalaxyCheckpoint) -> Result<Self, OrchestratorError> {
+        let mut orchestrator = Self::new()?;
+        orchestrator.restore_checkpoint(checkpoint)?;
+        Ok(orchestrator)
+    }
+
+    /// Creates the galaxy of a checkpoint in an orchestrator without planets, e.g. a new or reset one
+    pub(crate) fn restore_checkpoint(&mut self, checkpoint: GalaxyCheckpoint) -> Result<(), OrchestratorError> {
+        let mut topology = GalaxyGraph::new();
+        for planet_id in checkpoint.planets.keys() {
+            topology.add_planet(*planet_id);
+        }
+        for (planet_one, planet_two) in &checkpoint.links {
+            topology.add_link(*planet_one, *planet_two).map_err(|reason| OrchestratorError::Checkpoint { reason })?;
+        }
+        *self
+            .galaxy_topology
+            .write()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))? = topology;
+        self.destroyed_links = checkpoint.destroyed_links.into_iter().collect();
+        self.current_tick = checkpoint.tick;
+        publish_tick(checkpoint.tick);
+        self.metrics = GameMetrics::starting_at(checkpoint.tick);
+        settings::set_sunray_asteroid_sequence(checkpoint.sunray_asteroid_sequence);
+
+        for (planet_id, planet) in checkpoint.planets {
+            self.galaxy_lookup.insert(planet_id, planet.planet_type);
+            if !planet.config.is_null() {
+                self.planet_configs.insert(planet_id, planet.config);
+            }
+            //A planet dead before the checkpoint did not die in this game: no metrics, no journal event
+            if planet.status.is_dead() {
+                self.planets_status
+                    .write()
+                    .map_err(|_| OrchestratorError::lock_poisoned("planets status"))?
+                    .insert(planet_id, planet.status);
+                self.planet_death_ticks.insert(planet_id, checkpoint.tick);
+                continue;
+            }
+            self.add_planet(planet_id, planet.planet_type)?;
+            if let Some(state) = planet.internal_state {
+                self.pending_recharges.insert(planet_id, state.charged_cells());
+                self.planet_states.insert(planet_id, state);
+            }
+        }
+
+        for (explorer_id, explorer) in checkpoint.explorers {
+            self.restored_bags.insert(explorer_id, explorer.bag);
+            let sender_explorer = self.planet_channels.get(&explorer.planet_id).map(|(_, sender)| sender.clone());
+            match sender_explorer {
+                Some(sender_explorer) if !explorer.status.is_dead() => {
+                    self.add_explorer(explorer_id, explorer.planet_id, 0, sender_explorer);
+                    let (_, sender_to_explorer) = self.explorer_channels.get(&explorer_id).unwrap();
+                    self.planet_channels[&explorer.planet_id]
+                        .0
+                        .send(OrchestratorToPlanet::IncomingExplorerRequest {
+                            explorer_id,
+                            new_sender: sender_to_explorer.clone(),
+                        })
+                        .map_err(|_| OrchestratorError::disconnected(Actor::Planet(explorer.planet_id)))?;
+                }
+                //Explorers that were dead, or on a planet that is gone, stay dead
+                _ => {
+                    self.explorer_status
+                        .write()
+                        .map_err(|_| OrchestratorError::lock_poisoned("explorers status"))?
+                        .insert(explorer_id, Status::Dead);
+                    self.explorer_positions.insert(explorer_id, explorer.planet_id);
+                }
+            }
+        }
+
+        self.refresh_topology_analysis()?;
+        self.record_galaxy_loaded();
+        log_event!(LOG_ACTORS_ACTIVITY, self.current_tick, None, "CheckpointLoaded", "{} planets", self.galaxy_lookup.len());
+        Ok(())
+    }
+
+    /// Sends to every restored planet one sunray for each energy cell it had charged
+    fn recharge_restored_planets(&mut self) -> Result<(), OrchestratorError> {
+        let recharges = std::mem::take(&mut self.pending_recharges);
+        for (planet_id, sunrays) in recharges {
+            for _ in 0..sunrays {
+                if !self.is_planet_alive(planet_id) {
+                    break;
+                }
+                //A planet that cannot be recharged is handled like any other send failure
+                if self.send_sunray_to(planet_id).is_err() {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+//End of the game
+impl Orchestrator {
+    /// First of the conditions that is met, if any
+    ///
+    /// Resources are looked for in the last bags received, see `request_explorer_bags`
+    pub(crate) fn check_end_conditions(&self, conditions: &[EndCondition]) -> Option<GameOverReason> {
+        let planets_status = self.planets_status.read().unwrap();
+        let explorer_status = self.explorer_status.read().unwrap();
+        conditions.iter().find_map(|condition| match condition {
+            EndCondition::AllPlanetsDead => (!planets_status.is_empty()
+                && planets_status.values().all(|status| status.is_dead()))
+            .then_some(GameOverReason::AllPlanetsDead),
+            EndCondition::AllExplorersDead => (!explorer_status.is_empty()
+                && explorer_status.values().all(|status| status.is_dead()))
+            .then_some(GameOverReason::AllExplorersDead),
+            EndCondition::TickLimit { tick } => {
+                (self.current_tick >= *tick).then_some(GameOverReason::TickLimit { tick: self.current_tick })
+            }
+            EndCondition::ResourceCrafted { resource } => self
+                .explorer_bags
+                .iter()
+                .find(|(_, bag)| bag.iter().any(|content| resource_name(content) == resource_name(resource)))
+                .map(|(explorer_id, _)| GameOverReason::ResourceCrafted {
+                    explorer_id: *explorer_id,
+                    resource: resource.clone(),
+                }),
+        })
+    }
+
+    /// Final results of the game, with the last bags received from the explorers
+    pub(crate) fn scoreboard(&self, reason: GameOverReason) -> Scoreboard {
+        let planets = self
+            .planets_status
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(planet_id, status)| {
+                let birth = self.planet_birth_ticks.get(planet_id).copied().unwrap_or(0);
+                let end = self.planet_death_ticks.get(planet_id).copied().unwrap_or(self.current_tick);
+                (
+                    *planet_id,
+                    PlanetScore {
+                        planet_type: self.galaxy_lookup.get(planet_id).copied(),
+                        status: status.clone(),
+                        survived_ticks: end.saturating_sub(birth),
+                    },
+                )
+            })
+            .collect();
+        let explorers = self
+            .explorer_status
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(explorer_id, status)| {
+                let bag = self.explorer_bags.get(explorer_id).cloned().unwrap_or_default();
+                (*explorer_id, ExplorerScore::new(status.clone(), bag))
+            })
+            .collect();
+        Scoreboard {
+            tick: self.current_tick,
+            reason,
+            planets,
+            explorers,
+        }
+    }
+}
+
 // REVIEW function used for testing or to eliminate
 impl Orchestrator {
-    pub(crate) fn run_test(file_path: String) -> Result<(), String> {
+    pub(crate) fn run_test(file_path: String) -> Result<(), OrchestratorError> {
         //Init and check orchestrator
         let mut orchestrator = Orchestrator::new()?;
 
@@ -648,13 +1672,13 @@ impl Orchestrator {
         // for (id, status) in &self.planets_status{
         //     print!("({}, {:?})",id, status);
         // }
-        debug_println!("{:?}", self.planets_status);
+        log_event!(LOG_FN_INT_OPERATIONS, self.current_tick, None, "PlanetsState", "{:?}", self.planets_status);
     }
     pub(crate) fn print_galaxy_topology(&self) {
-        debug_println!("{:?}", self.galaxy_topology);
+        log_event!(LOG_FN_INT_OPERATIONS, self.current_tick, None, "GalaxyTopology", "{:?}", self.galaxy_topology);
     }
     pub(crate) fn print_orch(&self) {
-        debug_println!("Orchestrator running");
+        log_event!(LOG_FN_INT_OPERATIONS, self.current_tick, None, "Orchestrator", "running");
     }
 }
 
@@ -670,7 +1694,116 @@ impl Orchestrator {
         self.galaxy_topology.clone()
     }
 
-    pub(crate) fn get_game_status(&self) -> Result<(GalaxyTopology, PlanetStatus, ExplorerStatus), String> {
-        Ok((Arc::clone(&self.galaxy_topology), Arc::clone(&self.planets_status), Arc::clone(&self.explorer_status)))
+    /// Dumps topology, planet types and status in the given format, see `GalaxySnapshot::export_topology`
+    pub(crate) fn export_topology(&self, format: ExportFormat) -> Result<String, OrchestratorError> {
+        self.get_game_snapshot()?
+            .export_topology(format)
+            .map_err(OrchestratorError::invalid)
     }
-}
\ No newline at end of file
+
+    fn get_topology_snapshot(&self) -> Result<TopologySnapshot, OrchestratorError> {
+        //Rows are ordered by planet id
+        let (planet_ids, adjacency) = self
+            .galaxy_topology
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?
+            .to_adjacency_matrix();
+
+        Ok(TopologySnapshot {
+            planet_ids,
+            adjacency,
+            destroyed_links: self.destroyed_links.iter().copied().collect(),
+        })
+    }
+
+    /// Recomputes critical planets, components and distances of the live planets
+    ///
+    /// Called on every change of the links or of the planets alive
+    pub(crate) fn refresh_topology_analysis(&mut self) -> Result<(), OrchestratorError> {
+        let planets_status = self
+            .planets_status
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("planets status"))?;
+        //Planets not created yet are part of the galaxy, only dead ones are left out
+        let live_graph = self
+            .galaxy_topology
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("galaxy topology"))?
+            .subgraph(|id| !planets_status.get(&id).is_some_and(|status| status.is_dead()));
+        let analysis = TopologyAnalysis::compute(live_graph);
+        drop(planets_status);
+
+        *self
+            .topology_analysis
+            .write()
+            .map_err(|_| OrchestratorError::lock_poisoned("topology analysis"))? = analysis;
+        self.bump_version();
+        Ok(())
+    }
+
+    /// Build an owned snapshot of the current game status
+    ///
+    /// The locks are held only while copying, the UI never
+    /// gets access to the orchestrator internal state
+    pub(crate) fn get_game_snapshot(&self) -> Result<GalaxySnapshot, OrchestratorError> {
+        let topology = self.get_topology_snapshot()?;
+
+        let planets = self
+            .planets_status
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("planets status"))?
+            .iter()
+            .map(|(id, status)| {
+                (
+                    *id,
+                    PlanetSnapshot {
+                        status: status.clone(),
+                        planet_type: self.galaxy_lookup.get(id).copied(),
+                    },
+                )
+            })
+            .collect();
+
+        let explorers = self
+            .explorer_status
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("explorers status"))?
+            .iter()
+            .map(|(id, status)| {
+                (
+                    *id,
+                    ExplorerSnapshot {
+                        status: status.clone(),
+                        planet_id: self.explorer_positions.get(id).copied().unwrap_or_default(),
+                    },
+                )
+            })
+            .collect();
+
+        let analysis = self
+            .topology_analysis
+            .read()
+            .map_err(|_| OrchestratorError::lock_poisoned("topology analysis"))?
+            .clone();
+
+        Ok(GalaxySnapshot {
+            version: self.state_version,
+            tick: self.current_tick,
+            topology,
+            analysis,
+            planets,
+            explorers,
+        })
+    }
+}
+
+/// Text of a panic, panics raised with `panic!` carry a `&str` or a `String`
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
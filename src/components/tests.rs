@@ -9,6 +9,13 @@ use crate::components::orchestrator::Orchestrator;
 use crate::utils::registry::PlanetType;
 use crate::utils::Status;
 
+//Mock planet scripted with `behaviour`, see `MockBehaviour`
+#[cfg(test)]
+fn add_mock(orch: &mut Orchestrator, planet_id: u32, behaviour: serde_json::Value) {
+    orch.planet_configs.insert(planet_id, behaviour);
+    orch.add_planet(planet_id, PlanetType::MOCK).unwrap();
+}
+
 #[cfg(test)]
 mod tests_core_lifecycle {
     use super::*;
@@ -70,6 +77,7 @@ mod tests_actor_management {
 #[cfg(test)]
 mod tests_topology_logic {
     use super::*;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_topology_adj_list_creates_symmetric_matrix() {
@@ -80,9 +88,21 @@ mod tests_topology_logic {
         orch.initialize_galaxy_by_adj_list(adj_list).unwrap();
         
         let gtop = orch.galaxy_topology.read().unwrap();
-        assert_eq!(gtop[0][1], true);
-        assert_eq!(gtop[1][0], true);
-        assert_eq!(gtop[0][0], false);
+        assert_eq!(gtop.are_linked(0, 1), true);
+        assert_eq!(gtop.are_linked(1, 0), true);
+        assert_eq!(gtop.are_linked(0, 0), false);
+    }
+
+    #[test]
+    fn test_topology_one_sided_link_is_made_symmetric() {
+        let mut orch = Orchestrator::new().unwrap();
+        //Only planet 0 lists the link
+        orch.initialize_galaxy_by_adj_list(vec![vec![1], vec![]]).unwrap();
+
+        let gtop = orch.galaxy_topology.read().unwrap();
+        assert!(gtop.are_linked(0, 1));
+        assert!(gtop.are_linked(1, 0));
+        assert_eq!(gtop.neighbors(1), vec![0]);
     }
 
     #[test]
@@ -94,7 +114,44 @@ mod tests_topology_logic {
         orch.destroy_topology_link(0, 1).unwrap();
         
         let gtop = orch.galaxy_topology.read().unwrap();
-        assert_eq!(gtop[0][1], false);
+        assert_eq!(gtop.are_linked(0, 1), false);
+        assert_eq!(gtop.are_linked(1, 0), false);
+    }
+
+    #[test]
+    fn test_topology_destroy_missing_link_keeps_the_version() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.initialize_galaxy_by_adj_list(vec![vec![1], vec![0], vec![]]).unwrap();
+        let version = orch.state_version;
+
+        //0 and 2 were never linked, nothing changes
+        orch.destroy_topology_link(0, 2).unwrap();
+        assert_eq!(orch.state_version, version);
+
+        orch.destroy_topology_link(0, 1).unwrap();
+        assert!(orch.state_version > version);
+    }
+
+    #[test]
+    fn test_topology_keyed_by_sparse_planet_ids() {
+        let mut orch = Orchestrator::new().unwrap();
+        let neighbors = BTreeMap::from([(10, vec![500]), (500, vec![]), (7, vec![10])]);
+        orch.initialize_galaxy_by_neighbors(neighbors).unwrap();
+
+        let gtop = orch.galaxy_topology.read().unwrap();
+        assert_eq!(gtop.planet_ids(), vec![7, 10, 500]);
+        assert_eq!(gtop.neighbors(10), vec![7, 500]);
+        drop(gtop);
+
+        let topology = orch.get_game_snapshot().unwrap().topology;
+        assert_eq!(topology.planet_ids, vec![7, 10, 500]);
+        assert_eq!(topology.neighbors(500), vec![10]);
+    }
+
+    #[test]
+    fn test_topology_link_to_unknown_planet_errors() {
+        let mut orch = Orchestrator::new().unwrap();
+        assert!(orch.initialize_galaxy_by_adj_list(vec![vec![3]]).is_err());
     }
 
     #[test]
@@ -167,6 +224,1863 @@ mod tests_file_integration {
     }
 }
 
+#[cfg(test)]
+mod tests_snapshot {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_is_detached_from_internal_state() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.initialize_galaxy_by_adj_list(vec![vec![1], vec![0]]).unwrap();
+
+        let snapshot = orch.get_game_snapshot().unwrap();
+        orch.destroy_topology_link(0, 1).unwrap();
+
+        // The snapshot taken before the change must not see it
+        assert!(snapshot.topology.adjacency[0][1]);
+        assert!(!orch.get_game_snapshot().unwrap().topology.adjacency[0][1]);
+    }
+
+    #[test]
+    fn test_snapshot_version_changes_only_on_updates() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.set_planet_status(1, Status::Paused);
+
+        let first = orch.get_game_snapshot().unwrap();
+        let same = orch.get_game_snapshot().unwrap();
+        assert_eq!(first.version, same.version);
+
+        // Same status again, nothing changed
+        orch.set_planet_status(1, Status::Paused);
+        assert_eq!(orch.get_game_snapshot().unwrap().version, first.version);
+
+        orch.set_planet_status(1, Status::Dead);
+        let changed = orch.get_game_snapshot().unwrap();
+        assert!(changed.version > first.version);
+        assert_eq!(changed.planets.get(&1).unwrap().status, Status::Dead);
+    }
+}
+
+#[cfg(test)]
+mod tests_manual_commands {
+    use super::*;
+    use crate::messages::{CommandFailure, UiToGame};
+    use crate::utils::errors::{Actor, OrchestratorError};
+
+    #[test]
+    fn test_manual_add_planet_with_links_grows_topology() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.add_planet(0, PlanetType::OneMillionCrabs).unwrap();
+        orch.initialize_galaxy_by_adj_list(vec![vec![]]).unwrap();
+
+        orch.add_planet_with_links(1, PlanetType::OneMillionCrabs, &[0]).unwrap();
+
+        let gtop = orch.galaxy_topology.read().unwrap();
+        assert_eq!(gtop.len(), 2);
+        assert!(gtop.are_linked(0, 1) && gtop.are_linked(1, 0));
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Paused));
+    }
+
+    #[test]
+    fn test_manual_add_planet_with_unknown_neighbor_errors() {
+        let mut orch = Orchestrator::new().unwrap();
+
+        let result = orch.add_planet_with_links(1, PlanetType::OneMillionCrabs, &[42]);
+
+        assert!(result.is_err());
+        assert!(orch.planets_status.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_manual_commands_on_unknown_actors_error() {
+        let mut orch = Orchestrator::new().unwrap();
+
+        assert!(orch.send_sunray_to(3).is_err());
+        assert!(orch.send_asteroid_to(3).is_err());
+        assert!(orch.spawn_explorer(1, 3).is_err());
+        assert!(orch.kill_explorer(1).is_err());
+        assert!(orch.request_bag_content(1).is_err());
+    }
+
+    #[test]
+    fn test_manual_commands_errors_tell_what_went_wrong() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.add_planet(1, PlanetType::OneMillionCrabs).unwrap();
+        orch.add_planet(2, PlanetType::OneMillionCrabs).unwrap();
+        orch.planets_status.write().unwrap().insert(2, Status::Dead);
+
+        assert_eq!(orch.send_sunray_to(3), Err(OrchestratorError::UnknownPlanet { planet_id: 3 }));
+        assert_eq!(orch.send_sunray_to(2), Err(OrchestratorError::PlanetNotAlive { planet_id: 2 }));
+        assert_eq!(orch.kill_explorer(4), Err(OrchestratorError::UnknownExplorer { explorer_id: 4 }));
+        assert!(matches!(
+            orch.add_planet_with_links(1, PlanetType::OneMillionCrabs, &[]),
+            Err(OrchestratorError::InvalidCommand { .. })
+        ));
+    }
+
+    #[test]
+    fn test_manual_command_error_reaches_the_ui_as_json() {
+        let failure = CommandFailure {
+            command: UiToGame::SendSunray { planet_id: 3 },
+            reason: OrchestratorError::UnknownPlanet { planet_id: 3 }.to_string(),
+            error: OrchestratorError::UnknownPlanet { planet_id: 3 },
+        };
+
+        let line = serde_json::to_string(&failure).unwrap();
+
+        assert!(line.contains(r#""error":{"UnknownPlanet":{"planet_id":3}}"#));
+        assert_eq!(
+            OrchestratorError::disconnected(Actor::Planet(3)).to_string(),
+            "The channel of planet 3 is closed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_json_protocol {
+    use super::*;
+    use crate::messages::json::{decode_game_message, decode_ui_command, encode_game_message, encode_ui_command};
+    use crate::messages::{CommandReply, GameToUi, UiToGame};
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+
+    #[test]
+    fn test_json_ui_command_round_trip() {
+        let command = UiToGame::AddPlanet { planet_id: 7, planet_type: PlanetType::Ciuc, neighbors: vec![1, 2] };
+
+        let line = encode_ui_command(&command).unwrap();
+        assert!(!line.contains('\n'));
+
+        match decode_ui_command(&line).unwrap() {
+            UiToGame::AddPlanet { planet_id, planet_type, neighbors } => {
+                assert_eq!(planet_id, 7);
+                assert_eq!(planet_type, PlanetType::Ciuc);
+                assert_eq!(neighbors, vec![1, 2]);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        assert!(matches!(decode_ui_command("\"StartGame\"").unwrap(), UiToGame::StartGame));
+    }
+
+    #[test]
+    fn test_json_bag_content_uses_resource_names() {
+        let msg = GameToUi::CommandResult(Ok(CommandReply::BagContent {
+            explorer_id: 1,
+            bag: vec![
+                ResourceType::Basic(BasicResourceType::Hydrogen),
+                ResourceType::Complex(ComplexResourceType::AIPartner),
+            ],
+        }));
+
+        let line = encode_game_message(&msg).unwrap();
+        assert!(line.contains("[\"Hydrogen\",\"AIPartner\"]"));
+
+        match decode_game_message(&line).unwrap() {
+            GameToUi::CommandResult(Ok(CommandReply::BagContent { bag, .. })) => assert_eq!(bag.len(), 2),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_invalid_command_errors() {
+        assert!(decode_ui_command("{\"Teleport\":{}}").is_err());
+        assert!(decode_ui_command("not json").is_err());
+    }
+
+    //Output of the bridge, read by the test once the bridge has returned
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_bridge_answers_a_command_end_to_end() {
+        use crate::bridge::serve;
+        use crate::utils::errors::OrchestratorError;
+
+        let path = std::env::temp_dir().join(format!("omc_galaxy_bridge_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[[planets]]\nid = 1\ntype = \"MockPlanet\"\nneighbors = [2]\n\n[[planets]]\nid = 2\ntype = \"MockPlanet\"\nneighbors = [1]\n",
+        )
+        .unwrap();
+        let input = "{\"SendSunray\":{\"planet_id\":9}}\nnot json\n";
+        let output = SharedBuffer::default();
+
+        //The input ends after the two lines, like a client closing the connection
+        let result = serve(path.to_str().unwrap().to_string(), std::io::Cursor::new(input), output.clone());
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(result, Err(OrchestratorError::GameEnded));
+
+        let written = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let answer = written
+            .lines()
+            .find(|line| line.contains("CommandResult"))
+            .unwrap_or_else(|| panic!("no answer to the command in {written}"));
+        match decode_game_message(answer).unwrap() {
+            GameToUi::CommandResult(Err(failure)) => {
+                assert_eq!(failure.error, OrchestratorError::UnknownPlanet { planet_id: 9 });
+                assert!(matches!(failure.command, UiToGame::SendSunray { planet_id: 9 }));
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+        assert!(written.lines().any(|line| line.starts_with("{\"BridgeError\"")));
+    }
+}
+
+#[cfg(test)]
+mod tests_galaxy_file {
+    use super::*;
+    use crate::utils::galaxy_file::GalaxyDefinition;
+
+    #[test]
+    fn test_galaxy_file_parses_toml_with_named_types() {
+        let input = r#"
+            events = "SA"
+
+            [metadata]
+            name = "pair"
+
+            [[planets]]
+            id = 3
+            type = "one_million_crabs"
+            neighbors = [8]
+
+            [[planets]]
+            id = 8
+            type = "Ciuc"
+            config = { threshold = 2 }
+
+            [[explorers]]
+            id = 1
+            planet_id = 8
+        "#;
+
+        let def = GalaxyDefinition::from_toml(input).unwrap();
+
+        assert_eq!(def.metadata.name.as_deref(), Some("pair"));
+        assert_eq!(def.events.as_deref(), Some("SA"));
+        assert_eq!(def.planets.len(), 2);
+        assert_eq!(def.planets[0].neighbors, vec![8]);
+        assert_eq!(def.planets[1].config["threshold"], 2);
+        assert_eq!(def.explorers[0].planet_id, 8);
+        assert_eq!(PlanetType::from_name(&def.planets[0].planet_type), Some(PlanetType::OneMillionCrabs));
+    }
+
+    #[test]
+    fn test_galaxy_file_json_and_csv_describe_the_same_galaxy() {
+        let json = r#"{"planets": [{"id": 0, "type": "OneMillionCrabs", "neighbors": [1]}, {"id": 1, "type": "Ciuc"}]}"#;
+        let csv = "0, 4, 1\n\n1, 1";
+
+        let mut from_json = GalaxyDefinition::from_json(json).unwrap();
+        let mut from_csv = GalaxyDefinition::from_csv(csv).unwrap();
+
+        assert_eq!(from_json.planets[1].line, Some(1));
+        assert_eq!(from_csv.planets[1].line, Some(3));
+
+        //Apart from the lines the two files describe the same galaxy
+        for planet in from_json.planets.iter_mut().chain(from_csv.planets.iter_mut()) {
+            planet.line = None;
+        }
+        assert_eq!(from_json, from_csv);
+    }
+
+    #[test]
+    fn test_galaxy_file_unknown_type_name_is_rejected() {
+        assert_eq!(PlanetType::from_name("Pluto"), None);
+
+        let mut orch = Orchestrator::new().unwrap();
+        let def = GalaxyDefinition::from_json(r#"{"planets": [{"id": 0, "type": "Pluto"}]}"#).unwrap();
+        assert!(orch.initialize_galaxy_by_definition(def).is_err());
+    }
+
+    #[test]
+    fn test_galaxy_file_parse_errors_have_their_line() {
+        let toml = "[[planets]]\nid = 0\ntype = \"Ciuc\"\n\n[[planets]]\nid = \"one\"\n";
+        let error = GalaxyDefinition::from_toml(toml).unwrap_err();
+        assert!(error.is_error());
+        assert_eq!(error.line, Some(6));
+
+        let json = "{\n  \"planets\": [\n    {\"id\": 0, \"type\": \"Ciuc\",}\n  ]\n}";
+        assert_eq!(GalaxyDefinition::from_json(json).unwrap_err().line, Some(3));
+
+        let error = GalaxyDefinition::from_csv("0, 1, 1\n1, 4, x").unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.to_string(), "line 2: error: value 'x' is not a u32");
+    }
+}
+
+#[cfg(test)]
+mod tests_galaxy_validation {
+    use super::*;
+    use crate::utils::errors::OrchestratorError;
+    use crate::utils::galaxy_file::GalaxyDefinition;
+
+    #[test]
+    fn test_validation_reports_every_problem_with_its_line() {
+        let input = r#"
+[[planets]]
+id = 0
+type = "Ciuc"
+neighbors = [0, 7]
+
+[[planets]]
+id = 0
+type = "Ciuc"
+
+[[explorers]]
+id = 1
+planet_id = 9
+"#;
+        let def = GalaxyDefinition::from_toml(input).unwrap();
+        let diagnostics = def.validate(false);
+        let lines: Vec<Option<usize>> = diagnostics.iter().map(|d| d.line).collect();
+
+        assert!(diagnostics.iter().all(|d| d.is_error()));
+        assert_eq!(lines, vec![Some(7), Some(2), Some(2), Some(11)]);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "line 7: error: planet 0 is defined more than once (first definition at line 2)"
+        );
+        assert!(diagnostics[2].message.contains("planet 7, which is not defined"));
+    }
+
+    #[test]
+    fn test_validation_warns_about_disconnected_planets_only_when_asked() {
+        let def = GalaxyDefinition::from_csv("0, 1, 1\n1, 1\n2, 1\n3, 1, 2").unwrap();
+
+        assert!(def.validate(false).is_empty());
+
+        let diagnostics = def.validate(true);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(!diagnostics[0].is_error());
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert!(diagnostics[0].message.contains("{2, 3}"));
+    }
+
+    #[test]
+    fn test_invalid_galaxy_is_rejected_before_spawning_planets() {
+        let mut orch = Orchestrator::new().unwrap();
+        let def = GalaxyDefinition::from_csv("0, 1, 1\n1, 1, 5").unwrap();
+
+        let result = orch.initialize_galaxy_by_definition(def);
+
+        match result {
+            Err(OrchestratorError::InitFile { problems }) => {
+                assert_eq!(problems[0].line, Some(2));
+                assert!(problems[0].to_string().contains("line 2: error: planet 1 is linked to planet 5"));
+            }
+            other => panic!("expected an init file error, got {other:?}"),
+        }
+        assert!(orch.planet_channels.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_topology_export {
+    use super::*;
+    use crate::utils::export::ExportFormat;
+
+    fn damaged_galaxy() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.initialize_galaxy_by_adj_list(vec![vec![1, 2], vec![0], vec![0]]).unwrap();
+        orch.set_planet_status(0, Status::Running);
+        orch.set_planet_status(1, Status::Dead);
+        orch.set_planet_status(2, Status::Paused);
+        orch.destroy_topology_link(0, 2).unwrap();
+        orch
+    }
+
+    #[test]
+    fn test_export_dot_greys_dead_planets_and_dashes_destroyed_links() {
+        let dot = damaged_galaxy().export_topology(ExportFormat::Dot).unwrap();
+
+        assert!(dot.starts_with("graph galaxy {"));
+        assert!(dot.contains("1 [label=\"1\\n-\", fillcolor=grey85, fontcolor=grey50];"));
+        assert!(dot.contains("0 -- 1 [color=grey70];"));
+        assert!(dot.contains("0 -- 2 [style=dashed, color=grey50];"));
+    }
+
+    #[test]
+    fn test_export_json_lists_live_and_destroyed_links() {
+        let json = damaged_galaxy().export_topology(ExportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["planets"].as_array().unwrap().len(), 3);
+        assert_eq!(value["planets"][1]["status"], "Dead");
+        assert_eq!(
+            value["links"],
+            serde_json::json!([
+                { "from": 0, "to": 1, "destroyed": false },
+                { "from": 0, "to": 2, "destroyed": true },
+            ])
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_galaxy_generator {
+    use super::*;
+    use crate::utils::galaxy_file::GalaxyDefinition;
+    use crate::utils::generator::{GalaxyGenerator, GraphModel, TypeMix};
+    use crate::utils::registry::PlanetClass;
+
+    fn degrees(def: &GalaxyDefinition) -> Vec<usize> {
+        def.planets.iter().map(|p| p.neighbors.len()).collect()
+    }
+
+    #[test]
+    fn test_generator_same_seed_same_galaxy() {
+        let model = GraphModel::RandomConnected { extra_link_probability: 0.3 };
+
+        let first = GalaxyGenerator::new(12, model, 7).generate().unwrap();
+        let second = GalaxyGenerator::new(12, model, 7).generate().unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_generator_models_shape() {
+        let ring = GalaxyGenerator::new(6, GraphModel::Ring, 1).generate().unwrap();
+        assert!(degrees(&ring).iter().all(|d| *d == 2));
+
+        let star = GalaxyGenerator::new(5, GraphModel::Star, 1).generate().unwrap();
+        assert_eq!(degrees(&star), vec![4, 1, 1, 1, 1]);
+
+        let grid = GalaxyGenerator::new(9, GraphModel::Grid, 1).generate().unwrap();
+        assert_eq!(degrees(&grid), vec![2, 3, 2, 3, 4, 3, 2, 3, 2]);
+    }
+
+    #[test]
+    fn test_generator_random_models_are_valid_galaxies() {
+        let models = [
+            GraphModel::RandomConnected { extra_link_probability: 0.0 },
+            GraphModel::SmallWorld { neighbors: 2, rewire_probability: 0.5 },
+        ];
+        for model in models {
+            for seed in 0..20 {
+                let def = GalaxyGenerator::new(10, model, seed).generate().unwrap();
+                assert!(def.validate(false).is_empty(), "{model:?} seed {seed}");
+            }
+        }
+
+        //A spanning tree without extra links is connected
+        let tree = GalaxyGenerator::new(10, models[0], 3).generate().unwrap();
+        assert!(tree.validate(true).is_empty());
+        assert_eq!(degrees(&tree).iter().sum::<usize>(), 2 * 9);
+    }
+
+    #[test]
+    fn test_generator_type_mix() {
+        let mix = TypeMix::parse("4C + 3D").unwrap();
+        let def = GalaxyGenerator::new(7, GraphModel::Ring, 5).with_mix(mix).generate().unwrap();
+
+        let classes: Vec<PlanetClass> = def
+            .planets
+            .iter()
+            .map(|p| PlanetType::from_name(&p.planet_type).unwrap().class().unwrap())
+            .collect();
+        assert_eq!(classes.iter().filter(|c| **c == PlanetClass::C).count(), 4);
+        assert_eq!(classes.iter().filter(|c| **c == PlanetClass::D).count(), 3);
+
+        let wrong_total = GalaxyGenerator::new(8, GraphModel::Ring, 5).with_mix(TypeMix::parse("4C + 3D").unwrap());
+        assert!(wrong_total.generate().is_err());
+        assert!(TypeMix::parse("2B").is_ok_and(|mix| GalaxyGenerator::new(2, GraphModel::Ring, 0).with_mix(mix).generate().is_err()));
+        assert!(TypeMix::parse("3 Pluto").is_err());
+    }
+
+    #[test]
+    fn test_generator_csv_output_is_a_valid_init_file() {
+        let mix = TypeMix::parse("2 Ciuc + 2 OneMillionCrabs").unwrap();
+        let generator = GalaxyGenerator::new(4, GraphModel::Ring, 9).with_mix(mix);
+
+        let csv = generator.generate_csv().unwrap();
+        let reread = GalaxyDefinition::from_csv(&csv).unwrap();
+        let generated = generator.generate().unwrap();
+
+        for (read, expected) in reread.planets.iter().zip(&generated.planets) {
+            assert_eq!(read.id, expected.id);
+            assert_eq!(read.planet_type, expected.planet_type);
+            assert_eq!(read.neighbors, expected.neighbors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_topology_analysis {
+    use super::*;
+    use crate::utils::topology_analysis::TopologyAnalysis;
+
+    // 0 - 1 - 2 triangle with 3, then a tail 3 - 4
+    //      \ /
+    //       3 - 4
+    fn kite() -> Vec<Vec<u32>> {
+        vec![vec![1], vec![0, 2, 3], vec![1, 3], vec![1, 2, 4], vec![3]]
+    }
+
+    #[test]
+    fn test_analysis_finds_critical_planets_and_bridges() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.initialize_galaxy_by_adj_list(kite()).unwrap();
+
+        let analysis = orch.get_game_snapshot().unwrap().analysis;
+
+        assert_eq!(analysis.articulation_points, vec![1, 3]);
+        assert_eq!(analysis.bridges, vec![(0, 1), (3, 4)]);
+        assert_eq!(analysis.components, vec![vec![0, 1, 2, 3, 4]]);
+        assert_eq!(analysis.diameter, 3);
+        assert_eq!(analysis.shortest_path(0, 4), Some(vec![0, 1, 3, 4]));
+    }
+
+    #[test]
+    fn test_analysis_is_recomputed_after_destroying_links_and_planets() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.initialize_galaxy_by_adj_list(kite()).unwrap();
+
+        orch.destroy_topology_link(3, 4).unwrap();
+        let analysis = orch.topology_analysis.read().unwrap().clone();
+        assert_eq!(analysis.components, vec![vec![0, 1, 2, 3], vec![4]]);
+        assert!(!analysis.are_connected(0, 4));
+        assert_eq!(analysis.distance(0, 4), None);
+
+        orch.set_planet_status(1, Status::Dead);
+        let analysis = orch.topology_analysis.read().unwrap().clone();
+        assert_eq!(analysis.components, vec![vec![2, 3], vec![0], vec![4]]);
+        assert!(analysis.articulation_points.is_empty());
+    }
+
+    #[test]
+    fn test_analysis_of_empty_and_single_planet_galaxies() {
+        assert_eq!(TopologyAnalysis::from_adjacency(&[], &[], |_| true), TopologyAnalysis::default());
+
+        let single = TopologyAnalysis::from_adjacency(&[7], &[vec![false]], |_| true);
+        assert_eq!(single.components, vec![vec![7]]);
+        assert_eq!(single.diameter, 0);
+        assert_eq!(single.shortest_path(7, 7), Some(vec![7]));
+    }
+}
+
+#[cfg(test)]
+mod tests_runtime_planets {
+    use super::*;
+    use crate::messages::GameToUi;
+
+    fn small_galaxy() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.add_planet(0, PlanetType::OneMillionCrabs).unwrap();
+        orch.add_planet(1, PlanetType::OneMillionCrabs).unwrap();
+        orch.initialize_galaxy_by_adj_list(vec![vec![1], vec![0]]).unwrap();
+        orch
+    }
+
+    #[test]
+    fn test_added_planet_is_announced_to_the_ui() {
+        let mut orch = small_galaxy();
+        orch.ui_events.clear();
+
+        orch.add_planet_with_links(7, PlanetType::OneMillionCrabs, &[0, 1]).unwrap();
+
+        assert!(matches!(
+            orch.ui_events.pop_back(),
+            Some(GameToUi::PlanetAdded { planet_id: 7, neighbors, .. }) if neighbors == vec![0, 1]
+        ));
+    }
+
+    #[test]
+    fn test_remove_planet_drops_links_and_state() {
+        let mut orch = small_galaxy();
+        orch.add_planet_with_links(7, PlanetType::OneMillionCrabs, &[0]).unwrap();
+
+        orch.remove_planet(7).unwrap();
+
+        let gtop = orch.galaxy_topology.read().unwrap();
+        assert!(!gtop.contains(7));
+        assert_eq!(gtop.neighbors(0), vec![1]);
+        drop(gtop);
+        assert!(!orch.planets_status.read().unwrap().contains_key(&7));
+        assert!(!orch.galaxy_lookup.contains_key(&7));
+        assert!(!orch.planet_channels.contains_key(&7));
+        assert!(matches!(orch.ui_events.pop_back(), Some(GameToUi::PlanetRemoved { planet_id: 7 })));
+        assert_eq!(orch.topology_analysis.read().unwrap().components, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_remove_planet_with_explorers_errors() {
+        let mut orch = small_galaxy();
+        orch.explorer_positions.insert(3, 1);
+        orch.set_explorer_status(3, Status::Running);
+
+        assert!(orch.remove_planet(1).is_err());
+        assert!(orch.galaxy_topology.read().unwrap().contains(1));
+
+        orch.set_explorer_status(3, Status::Dead);
+        assert!(orch.remove_planet(1).is_ok());
+    }
+
+    #[test]
+    fn test_late_asteroid_ack_from_a_removed_planet_is_dropped() {
+        use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+        use std::time::Duration;
+
+        let mut orch = Orchestrator::new().unwrap();
+        orch.add_planet(1, PlanetType::MOCK).unwrap();
+        orch.start_all().unwrap();
+        orch.send_asteroid_to(1).unwrap();
+        let ack = loop {
+            let msg = orch.recevier_orch_planet.recv_timeout(Duration::from_secs(2)).unwrap();
+            if matches!(msg, PlanetToOrchestrator::AsteroidAck { .. }) {
+                break msg;
+            }
+            orch.handle_planet_message(msg).unwrap();
+        };
+
+        //The planet is removed while its ack is still in the queue
+        orch.remove_planet(1).unwrap();
+        assert_eq!(orch.handle_planet_message(ack), Ok(()));
+        assert!(!orch.planets_status.read().unwrap().contains_key(&1));
+    }
+
+    #[test]
+    fn test_remove_unknown_planet_errors() {
+        let mut orch = small_galaxy();
+
+        assert!(orch.remove_planet(42).is_err());
+        assert_eq!(orch.galaxy_topology.read().unwrap().len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_config {
+    use super::*;
+    use crate::utils::galaxy_file::GalaxyDefinition;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_is_checked_by_planet_type() {
+        let houston = PlanetType::HoustonWeHaveABorrow;
+
+        assert!(houston.check_config(&serde_json::Value::Null).is_ok());
+        assert!(houston.check_config(&json!({"basic_resource": "Oxygen", "rocket_strategy": "default"})).is_ok());
+        assert!(houston.check_config(&json!({"basic_resource": "gold"})).is_err());
+        assert!(houston.check_config(&json!({"rockets": 3})).is_err());
+        assert!(houston.check_config(&json!({"rocket_strategy": "emergency_reserve"})).is_ok());
+        assert!(houston.check_config(&json!({"rocket_strategy": "always"})).is_err());
+        assert!(PlanetType::Rustrelli.check_config(&json!({"request_limit": "none"})).is_ok());
+        assert!(PlanetType::Rustrelli.check_config(&json!({"request_limit": "fair_share"})).is_ok());
+        assert!(PlanetType::Ciuc.check_config(&json!({"threshold": 2})).is_err());
+    }
+
+    #[test]
+    fn test_immutable_cosmic_borrow_takes_its_ai_thresholds() {
+        let planet = PlanetType::ImmutableCosmicBorrow;
+
+        assert!(planet.check_config(&serde_json::Value::Null).is_ok());
+        assert!(planet
+            .check_config(&json!({"sunray_threshold": 0.5, "asteroid_threshold": 0.2, "sunray_window_secs": 5}))
+            .is_ok());
+        assert!(planet.check_config(&json!({"sunray_threshold": 1.5})).is_err());
+        assert!(planet.check_config(&json!({"energy_reserve": -1.0})).is_err());
+        assert!(planet.check_config(&json!({"threshold": 0.5})).is_err());
+    }
+
+    #[test]
+    fn test_invalid_config_is_reported_with_its_line() {
+        let input = r#"
+[[planets]]
+id = 0
+type = "Ciuc"
+
+[[planets]]
+id = 1
+type = "houston_we_have_a_borrow"
+config = { basic_resource = "gold" }
+"#;
+        let def = GalaxyDefinition::from_toml(input).unwrap();
+
+        let diagnostics = def.validate(false);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(6));
+        assert!(diagnostics[0].message.contains("unknown basic resource 'gold'"));
+    }
+
+    #[test]
+    fn test_same_type_with_different_configs() {
+        let input = r#"
+            [[planets]]
+            id = 0
+            type = "houston_we_have_a_borrow"
+            neighbors = [1]
+            config = { basic_resource = "oxygen" }
+
+            [[planets]]
+            id = 1
+            type = "houston_we_have_a_borrow"
+            config = { basic_resource = "carbon" }
+        "#;
+        let mut orch = Orchestrator::new().unwrap();
+
+        orch.initialize_galaxy_by_definition(GalaxyDefinition::from_toml(input).unwrap()).unwrap();
+
+        assert_eq!(orch.planet_configs[&0]["basic_resource"], "oxygen");
+        assert_eq!(orch.planet_configs[&1]["basic_resource"], "carbon");
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Paused));
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_capabilities {
+    use super::*;
+    use crate::utils::registry::{EnergyCells, Limit, PlanetCapabilities, PlanetClass};
+    use common_game::components::resource::BasicResourceType;
+
+    #[test]
+    fn test_every_builtin_planet_declares_its_resources() {
+        for planet_type in PlanetType::BUILTIN {
+            let capabilities = planet_type.capabilities().unwrap();
+            let basic = capabilities.basic_resources.as_ref().unwrap();
+            let complex = capabilities.complex_resources.as_ref().unwrap();
+
+            assert!(!basic.is_empty(), "{planet_type} generates nothing");
+            assert_eq!(complex.is_empty(), !capabilities.can_combine(), "{planet_type}");
+            let cells = capabilities.energy_cell_count.unwrap();
+            assert_eq!(cells > 1, capabilities.has_many_energy_cells(), "{planet_type}");
+        }
+    }
+
+    #[test]
+    fn test_capabilities_of_each_planet() {
+        use common_game::components::resource::ComplexResourceType;
+
+        let ciuc = PlanetType::Ciuc.capabilities().unwrap();
+        assert_eq!(ciuc.basic_resources, Some(vec![BasicResourceType::Carbon]));
+        assert_eq!(ciuc.energy_cell_count, Some(5));
+
+        let crabs = PlanetType::OneMillionCrabs.capabilities().unwrap();
+        assert_eq!(crabs.basic_resources.as_ref().map(Vec::len), Some(4));
+        assert_eq!(crabs.complex_resources, Some(vec![]));
+
+        let rustrelli = PlanetType::Rustrelli.capabilities().unwrap();
+        assert_eq!(rustrelli.basic_resources, Some(vec![BasicResourceType::Oxygen]));
+        assert_eq!(rustrelli.energy_cell_count, Some(1));
+        assert!(rustrelli.complex_resources.unwrap().contains(&ComplexResourceType::AIPartner));
+
+        assert_eq!(
+            PlanetType::ImmutableCosmicBorrow.capabilities().unwrap().basic_resources,
+            Some(vec![BasicResourceType::Silicon])
+        );
+        assert_eq!(
+            PlanetType::RustyCrab.capabilities().unwrap().basic_resources,
+            Some(vec![BasicResourceType::Hydrogen])
+        );
+    }
+
+    #[test]
+    fn test_capabilities_follow_the_class_rules() {
+        let ciuc = PlanetType::Ciuc.capabilities().unwrap();
+        assert_eq!(ciuc.class, PlanetClass::A);
+        assert!(ciuc.has_many_energy_cells() && ciuc.can_build_rockets() && !ciuc.can_combine());
+
+        let crabs = PlanetType::OneMillionCrabs.capabilities().unwrap();
+        assert_eq!(crabs.rules.generation_rules, Limit::Unbounded);
+        assert!(!crabs.can_build_rockets() && !crabs.can_combine());
+
+        let houston = PlanetType::HoustonWeHaveABorrow.capabilities().unwrap();
+        assert_eq!(houston.rules.energy_cells, EnergyCells::One);
+        assert!(houston.can_build_rockets() && houston.can_combine());
+        assert!(houston.may_generate(BasicResourceType::Silicon));
+        assert!(ciuc.may_generate(BasicResourceType::Carbon) && !ciuc.may_generate(BasicResourceType::Oxygen));
+        //Planets that do not declare their resources may generate any of them
+        let undeclared = PlanetCapabilities::of_class(PlanetClass::B);
+        assert!(undeclared.basic_resources.is_none() && undeclared.may_generate(BasicResourceType::Carbon));
+        assert_eq!(undeclared.energy_cell_count, Some(1));
+        assert_eq!(PlanetCapabilities::of_class(PlanetClass::D).energy_cell_count, None);
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_registration {
+    use super::*;
+    use crate::utils::PlanetFactory;
+    use crate::utils::galaxy_file::GalaxyDefinition;
+    use crate::utils::registry::{PlanetCapabilities, PlanetClass, register_planet};
+
+    fn crab_factory() -> PlanetFactory {
+        Box::new(|rx_o, tx_o, rx_e, planet_id, _config: &serde_json::Value| {
+            one_million_crabs::planet::create_planet(rx_o, tx_o, rx_e, planet_id)
+        })
+    }
+
+    #[test]
+    fn test_csv_type_ids_match_the_builtin_types() {
+        assert_eq!(PlanetType::from_type_id(6), PlanetType::RustyCrab);
+        for planet_type in PlanetType::BUILTIN {
+            assert_eq!(PlanetType::from_type_id(planet_type.type_id().unwrap()), planet_type);
+        }
+    }
+
+    #[test]
+    fn test_registered_planet_is_found_by_name() {
+        let planet_type =
+            register_planet("TestCrabOne", PlanetCapabilities::of_class(PlanetClass::B), crab_factory()).unwrap();
+
+        assert_eq!(planet_type.type_id(), None);
+        assert_eq!(PlanetType::from_name("test_crab_one"), Some(planet_type));
+        assert_eq!(planet_type.class(), Some(PlanetClass::B));
+        assert!(PlanetType::all().contains(&planet_type));
+
+        let json = serde_json::to_string(&planet_type).unwrap();
+        assert_eq!(json, "\"TestCrabOne\"");
+        assert_eq!(serde_json::from_str::<PlanetType>(&json).unwrap(), planet_type);
+    }
+
+    #[test]
+    fn test_registering_a_used_name_errors() {
+        let capabilities = PlanetCapabilities::of_class(PlanetClass::D);
+
+        assert!(register_planet("one_million_crabs", capabilities.clone(), crab_factory()).is_err());
+        assert!(register_planet("random", capabilities.clone(), crab_factory()).is_err());
+        register_planet("TestCrabTwo", capabilities.clone(), crab_factory()).unwrap();
+        assert!(register_planet("test-crab-two", capabilities, crab_factory()).is_err());
+    }
+
+    #[test]
+    fn test_galaxy_file_uses_registered_planets_by_name() {
+        let planet_type =
+            register_planet("TestCrabThree", PlanetCapabilities::of_class(PlanetClass::D), crab_factory()).unwrap();
+        let def = GalaxyDefinition::from_csv("0, test_crab_three, 1\n1, 4").unwrap();
+        assert!(def.validate(false).is_empty());
+        assert_eq!(def.to_csv().unwrap(), "0, TestCrabThree, 1\n1, 4\n");
+
+        let mut orch = Orchestrator::new().unwrap();
+        orch.initialize_galaxy_by_definition(def).unwrap();
+
+        assert_eq!(orch.galaxy_lookup[&0], planet_type);
+        assert_eq!(orch.planets_status.read().unwrap().get(&0), Some(&Status::Paused));
+    }
+
+    #[test]
+    fn test_unregistered_custom_type_errors_instead_of_panicking() {
+        let unknown = PlanetType::Custom("NeverRegistered");
+        let (_tx_o, rx_o) = crossbeam_channel::unbounded();
+        let (tx_p, _rx_p) = crossbeam_channel::unbounded();
+        let (_tx_e, rx_e) = crossbeam_channel::unbounded();
+
+        assert_eq!(unknown.capabilities(), None);
+        assert_eq!(unknown.class(), None);
+        assert!(unknown.check_config(&serde_json::Value::Null).is_err());
+        assert!(unknown.create_planet(rx_o, tx_p, rx_e, 0, &serde_json::Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_generator_ignores_registered_planets() {
+        use crate::utils::generator::{GalaxyGenerator, GraphModel, TypeMix};
+
+        register_planet("TestCrabFour", PlanetCapabilities::of_class(PlanetClass::B), crab_factory()).unwrap();
+        let def = GalaxyGenerator::new(20, GraphModel::Ring, 3).generate().unwrap();
+
+        assert!(def
+            .planets
+            .iter()
+            .all(|planet| PlanetType::BUILTIN.contains(&PlanetType::from_name(&planet.planet_type).unwrap())));
+        //Only the builtin types count for a class, there is no builtin class B planet
+        let mix = TypeMix::parse("2B").unwrap();
+        assert!(GalaxyGenerator::new(2, GraphModel::Ring, 3).with_mix(mix).generate().is_err());
+        let mix = TypeMix::parse("2 test_crab_four").unwrap();
+        assert!(GalaxyGenerator::new(2, GraphModel::Ring, 3).with_mix(mix).generate().is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests_mock_planets {
+    use super::*;
+    use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+    use serde_json::json;
+    use std::time::Duration;
+
+    //Handles the planet messages like the game loop until the next ack, returns the planet that sent it
+    fn handle_next_ack(orch: &mut Orchestrator) -> u32 {
+        loop {
+            let msg = orch.recevier_orch_planet.recv_timeout(Duration::from_secs(2)).unwrap();
+            let ack = match &msg {
+                PlanetToOrchestrator::SunrayAck { planet_id } | PlanetToOrchestrator::AsteroidAck { planet_id, .. } => {
+                    Some(*planet_id)
+                }
+                _ => None,
+            };
+            orch.handle_planet_message(msg).unwrap();
+            if let Some(planet_id) = ack {
+                return planet_id;
+            }
+        }
+    }
+
+    #[test]
+    fn test_mock_with_rockets_survives_and_mock_without_dies() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"always_rocket": true}));
+        add_mock(&mut orch, 2, serde_json::Value::Null);
+        orch.start_all().unwrap();
+
+        orch.send_sunray_to(1).unwrap();
+        orch.send_sunray_to(2).unwrap();
+        handle_next_ack(&mut orch);
+        handle_next_ack(&mut orch);
+        orch.send_asteroid_to(1).unwrap();
+        orch.send_asteroid_to(2).unwrap();
+        handle_next_ack(&mut orch);
+        handle_next_ack(&mut orch);
+
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Running));
+        assert_eq!(orch.planets_status.read().unwrap().get(&2), Some(&Status::Dead));
+    }
+
+    #[test]
+    fn test_mock_dies_after_the_given_asteroids() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"always_rocket": true, "dies_after": 2}));
+        orch.start_all().unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Running));
+            orch.send_sunray_to(1).unwrap();
+            handle_next_ack(&mut orch);
+            orch.send_asteroid_to(1).unwrap();
+            handle_next_ack(&mut orch);
+        }
+
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Dead));
+    }
+
+    #[test]
+    fn test_mock_delays_or_never_sends_acks() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"ack_delay_ms": 300}));
+        add_mock(&mut orch, 2, json!({"never_ack": true}));
+        orch.start_all().unwrap();
+
+        orch.send_sunray_to(1).unwrap();
+        orch.send_sunray_to(2).unwrap();
+
+        assert!(orch.recevier_orch_planet.recv_timeout(Duration::from_millis(100)).is_err());
+        assert_eq!(handle_next_ack(&mut orch), 1);
+        assert!(orch.recevier_orch_planet.recv_timeout(Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn test_mock_config_is_validated() {
+        assert!(PlanetType::MOCK.check_config(&json!({"dies_after": 1})).is_ok());
+        assert!(PlanetType::MOCK.check_config(&json!({"explode": true})).is_err());
+    }
+
+    #[test]
+    fn test_mock_is_never_chosen_at_random() {
+        use crate::utils::registry::PlanetClass;
+
+        assert!(!PlanetType::all().contains(&PlanetType::MOCK));
+        assert!(!PlanetType::of_class(PlanetClass::A).contains(&PlanetType::MOCK));
+        assert!((0..50).all(|_| PlanetType::random() != PlanetType::MOCK));
+        //Checkpoints and journals still find it by name
+        assert_eq!(PlanetType::from_name("MockPlanet"), Some(PlanetType::MOCK));
+    }
+}
+
+#[cfg(test)]
+mod tests_explorer {
+    use crate::components::explorer::ExplorerState;
+    use crate::components::explorer::harness::ExplorerHarness;
+    use common_game::components::energy_cell::EnergyCell;
+    use common_game::components::forge::Forge;
+    use common_game::components::resource::{BasicResource, BasicResourceType, Generator, ResourceType};
+    use common_game::protocols::orchestrator_explorer::{ExplorerToOrchestrator, OrchestratorToExplorer};
+    use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+    use std::collections::HashSet;
+
+    //Real resources, made like a planet makes them: a charged cell for each one.
+    //There can be a single forge in a process, so every test calls this at most once
+    fn make_carbon(count: usize) -> Vec<BasicResource> {
+        let forge = Forge::new().unwrap();
+        let mut generator = Generator::new();
+        generator.add(BasicResourceType::Carbon).unwrap();
+        (0..count)
+            .map(|_| {
+                let mut cell = EnergyCell::new();
+                cell.charge(forge.generate_sunray());
+                BasicResource::Carbon(generator.make_carbon(&mut cell).unwrap())
+            })
+            .collect()
+    }
+
+    fn generate(harness: &mut ExplorerHarness, reply: Option<BasicResource>) {
+        harness.script_planet_reply(PlanetToExplorer::GenerateResourceResponse { resource: reply });
+        harness.send(OrchestratorToExplorer::GenerateResourceRequest {
+            to_generate: BasicResourceType::Carbon,
+        });
+        harness.step();
+    }
+
+    #[test]
+    fn test_explorer_buffers_requests_until_started() {
+        let mut harness = ExplorerHarness::new(1, 3);
+        assert_eq!(harness.state(), &ExplorerState::WaitingToStartExplorerAI);
+
+        harness.send(OrchestratorToExplorer::CurrentPlanetRequest);
+        harness.step();
+        harness.assert_no_reply();
+        assert_eq!(harness.buffered_messages(), (1, 0));
+
+        harness.send(OrchestratorToExplorer::StartExplorerAI);
+        harness.step();
+        assert!(matches!(harness.expect_reply(), ExplorerToOrchestrator::StartExplorerAIResult { explorer_id: 1 }));
+        assert_eq!(harness.state(), &ExplorerState::Idle);
+
+        //With no new message the explorer goes through its buffers
+        harness.step();
+        assert!(matches!(
+            harness.expect_reply(),
+            ExplorerToOrchestrator::CurrentPlanetResult { explorer_id: 1, planet_id: 3 }
+        ));
+        assert_eq!(harness.buffered_messages(), (0, 0));
+        assert_eq!(harness.planet_id(), 3);
+    }
+
+    #[test]
+    fn test_explorer_learns_the_neighbours_of_its_planet() {
+        let mut harness = ExplorerHarness::started(1, 3);
+
+        harness.send(OrchestratorToExplorer::NeighborsResponse { neighbors: vec![4, 5] });
+        harness.step();
+
+        assert_eq!(harness.known_neighbours(3), Some(HashSet::from([4, 5])));
+        //The neighbours are known planets now, but nothing is known about them yet
+        assert_eq!(harness.known_neighbours(4), None);
+        assert_eq!(harness.known_basic_resources(4), None);
+        assert_eq!(harness.known_complex_resources(5), None);
+        assert_eq!(harness.state(), &ExplorerState::Idle);
+    }
+
+    #[test]
+    fn test_explorer_asks_its_planet_to_generate() {
+        let mut harness = ExplorerHarness::started(1, 3);
+        let carbon = ResourceType::Basic(BasicResourceType::Carbon);
+        let mut resources = make_carbon(2).into_iter();
+
+        generate(&mut harness, resources.next());
+        let requests = harness.take_planet_requests();
+        assert_eq!(requests.len(), 1);
+        assert!(matches!(
+            requests[0],
+            ExplorerToPlanet::GenerateResourceRequest { explorer_id: 1, resource: BasicResourceType::Carbon }
+        ));
+        assert_eq!(harness.bag(), vec![carbon.clone()]);
+
+        //The planet could not generate anything, the bag does not change
+        generate(&mut harness, None);
+        assert_eq!(harness.bag(), vec![carbon.clone()]);
+
+        generate(&mut harness, resources.next());
+        assert_eq!(harness.bag(), vec![carbon.clone(), carbon]);
+        assert_eq!(harness.take_planet_requests().len(), 2);
+    }
+
+    #[test]
+    fn test_explorer_sends_its_bag() {
+        let mut harness = ExplorerHarness::started(2, 0);
+        let bag_content = |harness: &mut ExplorerHarness| {
+            harness.send(OrchestratorToExplorer::BagContentRequest);
+            harness.step();
+            match harness.expect_reply() {
+                ExplorerToOrchestrator::BagContentResponse { explorer_id, bag_content } => {
+                    assert_eq!(explorer_id, 2);
+                    bag_content
+                }
+                _ => panic!("expected the bag content"),
+            }
+        };
+
+        assert!(bag_content(&mut harness).is_empty());
+
+        generate(&mut harness, make_carbon(1).pop());
+        assert_eq!(bag_content(&mut harness), vec![ResourceType::Basic(BasicResourceType::Carbon)]);
+        assert_eq!(harness.bag(), vec![ResourceType::Basic(BasicResourceType::Carbon)]);
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_liveness {
+    use super::*;
+    use crate::messages::GameToUi;
+    use serde_json::json;
+    use std::time::{Duration, Instant};
+
+    fn status(orch: &Orchestrator, planet_id: u32) -> Option<Status> {
+        orch.planets_status.read().unwrap().get(&planet_id).cloned()
+    }
+
+    //Far enough in the future that every pending request is late
+    fn later() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    #[test]
+    fn test_liveness_planet_without_ack_is_unresponsive() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"never_ack": true}));
+        add_mock(&mut orch, 2, serde_json::Value::Null);
+        orch.start_all().unwrap();
+
+        orch.send_sunray_to_all().unwrap();
+        //The healthy planet answers before the deadline
+        std::thread::sleep(Duration::from_millis(200));
+        orch.check_planet_deadlines(Instant::now()).unwrap();
+        assert_eq!(status(&orch, 2), Some(Status::Running));
+
+        orch.check_planet_deadlines(later()).unwrap();
+
+        assert_eq!(status(&orch, 1), Some(Status::Unresponsive));
+        assert_eq!(status(&orch, 2), Some(Status::Running));
+        assert!(orch.is_planet_alive(1));
+        assert!(matches!(
+            orch.ui_events.pop_front(),
+            Some(GameToUi::PlanetUnresponsive { planet_id: 1, killed: false })
+        ));
+        assert!(orch.ui_events.is_empty());
+    }
+
+    #[test]
+    fn test_liveness_unresponsive_planet_is_reported_once_and_skipped() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"never_ack": true}));
+        orch.start_all().unwrap();
+        orch.send_sunray_to_all().unwrap();
+
+        orch.check_planet_deadlines(later()).unwrap();
+        orch.send_asteroid_to_all().unwrap();
+        orch.check_planet_deadlines(later()).unwrap();
+
+        assert_eq!(orch.ui_events.len(), 1);
+        //Only the first sunray is waiting for an answer
+        assert_eq!(orch.pending_acks.get(&1).map(|deadlines| deadlines.len()), Some(1));
+    }
+
+    #[test]
+    fn test_liveness_late_ack_brings_the_planet_back() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"ack_delay_ms": 300}));
+        orch.start_all().unwrap();
+        orch.send_sunray_to(1).unwrap();
+
+        orch.check_planet_deadlines(later()).unwrap();
+        assert_eq!(status(&orch, 1), Some(Status::Unresponsive));
+
+        let ack = orch.recevier_orch_planet.recv_timeout(Duration::from_secs(2)).unwrap();
+        orch.handle_planet_message(ack).unwrap();
+
+        assert_eq!(status(&orch, 1), Some(Status::Running));
+        assert!(orch.pending_acks.is_empty());
+    }
+
+    #[test]
+    fn test_liveness_late_asteroid_ack_of_a_killed_planet_is_not_an_error() {
+        use common_game::protocols::orchestrator_planet::PlanetToOrchestrator;
+
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"ack_delay_ms": 300}));
+        orch.kill_unresponsive_planets = true;
+        orch.start_all().unwrap();
+        orch.send_asteroid_to(1).unwrap();
+
+        orch.check_planet_deadlines(later()).unwrap();
+        assert_eq!(status(&orch, 1), Some(Status::Dead));
+
+        let ack = loop {
+            let msg = orch.recevier_orch_planet.recv_timeout(Duration::from_secs(2)).unwrap();
+            if matches!(msg, PlanetToOrchestrator::AsteroidAck { .. }) {
+                break msg;
+            }
+        };
+        assert_eq!(orch.handle_planet_message(ack), Ok(()));
+        assert_eq!(status(&orch, 1), Some(Status::Dead));
+    }
+
+    #[test]
+    fn test_liveness_unresponsive_planet_can_be_killed_and_isolated() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"never_ack": true}));
+        add_mock(&mut orch, 2, serde_json::Value::Null);
+        add_mock(&mut orch, 3, serde_json::Value::Null);
+        {
+            let mut gtop = orch.galaxy_topology.write().unwrap();
+            for id in [1, 2, 3] {
+                gtop.add_planet(id);
+            }
+            gtop.add_link(1, 2).unwrap();
+            gtop.add_link(1, 3).unwrap();
+        }
+        orch.kill_unresponsive_planets = true;
+        orch.start_all().unwrap();
+        orch.send_sunray_to(1).unwrap();
+
+        orch.check_planet_deadlines(later()).unwrap();
+
+        assert_eq!(status(&orch, 1), Some(Status::Dead));
+        assert!(orch.galaxy_topology.read().unwrap().neighbors(1).is_empty());
+        assert!(orch.destroyed_links.contains(&(1, 2)));
+        assert!(orch.destroyed_links.contains(&(1, 3)));
+        assert!(orch.pending_acks.is_empty());
+        assert!(matches!(
+            orch.ui_events.pop_front(),
+            Some(GameToUi::PlanetUnresponsive { planet_id: 1, killed: true })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests_planet_crashes {
+    use super::*;
+    use crate::messages::GameToUi;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn wait_thread_end(orch: &Orchestrator, planet_id: u32) {
+        for _ in 0..200 {
+            if orch.planet_threads[&planet_id].is_finished() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the thread of planet {planet_id} is still running");
+    }
+
+    #[test]
+    fn test_crash_panicking_planet_is_reported() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"panic_on_sunray": true}));
+        add_mock(&mut orch, 2, serde_json::Value::Null);
+        orch.galaxy_topology.write().unwrap().add_planet(1);
+        orch.galaxy_topology.write().unwrap().add_planet(2);
+        orch.galaxy_topology.write().unwrap().add_link(1, 2).unwrap();
+        orch.start_all().unwrap();
+
+        orch.send_sunray_to(1).unwrap();
+        wait_thread_end(&orch, 1);
+        orch.check_planet_threads().unwrap();
+
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Crashed));
+        assert!(orch.planet_crashes[&1].contains("mock planet hit by a sunray"));
+        assert!(!orch.planet_channels.contains_key(&1));
+        assert!(!orch.is_planet_alive(1));
+        assert!(matches!(
+            orch.ui_events.pop_front(),
+            Some(GameToUi::PlanetCrashed { planet_id: 1, .. })
+        ));
+        //The crashed planet is no longer part of the live topology
+        let analysis = orch.topology_analysis.read().unwrap();
+        assert!(analysis.components.iter().all(|component| !component.contains(&1)));
+    }
+
+    #[test]
+    fn test_crash_found_by_a_failing_send_does_not_stop_the_others() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, json!({"panic_on_sunray": true}));
+        add_mock(&mut orch, 2, serde_json::Value::Null);
+        orch.start_all().unwrap();
+        orch.send_sunray_to(1).unwrap();
+        wait_thread_end(&orch, 1);
+
+        assert!(orch.send_sunray_to_all().is_ok());
+
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Crashed));
+        assert_eq!(orch.pending_acks.get(&2).map(|deadlines| deadlines.len()), Some(1));
+    }
+
+    #[test]
+    fn test_crash_killed_planet_is_not_a_crash() {
+        let mut orch = Orchestrator::new().unwrap();
+        add_mock(&mut orch, 1, serde_json::Value::Null);
+        orch.start_all().unwrap();
+        //Without a rocket the first asteroid kills the planet
+        orch.send_asteroid_to(1).unwrap();
+        let ack = orch.recevier_orch_planet.recv_timeout(Duration::from_secs(2)).unwrap();
+        orch.handle_planet_message(ack).unwrap();
+        wait_thread_end(&orch, 1);
+
+        orch.check_planet_threads().unwrap();
+
+        assert_eq!(orch.planets_status.read().unwrap().get(&1), Some(&Status::Dead));
+        assert!(orch.planet_crashes.is_empty());
+        assert!(orch.ui_events.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_logging {
+    use super::*;
+    use crate::settings;
+    use crate::utils::errors::Actor;
+    use crate::utils::logging::{LogSink, channel_of_rank, channel_rank, write_event};
+    use common_game::logging::Channel;
+
+    #[test]
+    fn test_logging_channel_ranks_round_trip() {
+        for rank in 0..5 {
+            assert_eq!(channel_rank(&channel_of_rank(rank)), rank);
+        }
+        assert!(channel_rank(&Channel::Error) < channel_rank(&Channel::Trace));
+    }
+
+    #[test]
+    fn test_logging_emits_common_game_events_above_the_level() {
+        let path = std::env::temp_dir().join(format!("omc_galaxy_log_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        settings::set_log_sink(LogSink::File(path.clone())).unwrap();
+        settings::set_log_level(Channel::Info);
+
+        write_event(Channel::Info, 7, Some(Actor::Planet(3)), "TestLoggingKept", "rocket: true".to_string());
+        write_event(Channel::Trace, 7, None, "TestLoggingDropped", String::new());
+
+        settings::set_log_sink(LogSink::Stderr).unwrap();
+        settings::set_log_level(Channel::Warning);
+        let content = std::fs::read_to_string(&path).unwrap();
+        //The line is the common_game LogEvent, with the details in its payload
+        let line = content
+            .lines()
+            .find(|line| line.contains("TestLoggingKept"))
+            .expect("the info event is missing");
+        assert!(line.contains("rocket: true"), "{line}");
+        assert!(line.contains("planet 3"), "{line}");
+        assert!(!content.contains("TestLoggingDropped"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+#[cfg(test)]
+mod tests_journal {
+    use super::*;
+    use crate::messages::UiToGame;
+    use crate::utils::journal::{GalaxyReplay, Journal, JournalEntry, JournalEvent};
+    use std::collections::BTreeMap;
+
+    fn journal_path(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("omc_galaxy_journal_{name}_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn to_jsonl(entries: &[JournalEntry]) -> String {
+        entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn test_journal_records_galaxy_and_status_changes() {
+        let path = journal_path("record");
+        let mut orch = Orchestrator::new().unwrap();
+        orch.journal = Some(Journal::create(&path).unwrap());
+        orch.galaxy_lookup.insert(1, PlanetType::MOCK);
+        orch.galaxy_lookup.insert(2, PlanetType::MOCK);
+        orch.initialize_galaxy_by_neighbors(BTreeMap::from([(1, vec![2]), (2, vec![1])]))
+            .unwrap();
+        orch.advance_tick();
+        orch.remove_link(1, 2).unwrap();
+        orch.record(JournalEvent::UiCommand(UiToGame::StopGame));
+        orch.send_planet_kill_to_all().unwrap();
+
+        let replay = GalaxyReplay::from_file(&path).unwrap();
+        let entries = replay.entries();
+        assert!(entries.iter().any(|entry| matches!(
+            &entry.event,
+            JournalEvent::GalaxyLoaded { planets, links } if planets.len() == 2 && links == &vec![(1, 2)]
+        )));
+        assert!(entries.iter().any(|entry| matches!(
+            entry.event,
+            JournalEvent::PlanetStatus { planet_id: 1, status: Status::Paused }
+        )));
+        let destroyed = entries
+            .iter()
+            .find(|entry| matches!(entry.event, JournalEvent::LinkDestroyed { .. }))
+            .expect("the destroyed link is missing");
+        assert_eq!(destroyed.tick, 1);
+        assert!(matches!(entries.last().unwrap().event, JournalEvent::UiCommand(UiToGame::StopGame)));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_journal_records_explorer_messages() {
+        use common_game::components::resource::{BasicResourceType, ResourceType};
+        use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+
+        let path = journal_path("explorer_messages");
+        let mut orch = Orchestrator::new().unwrap();
+        orch.journal = Some(Journal::create(&path).unwrap());
+        orch.advance_tick();
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 4,
+            bag_content: vec![ResourceType::Basic(BasicResourceType::Carbon)],
+        })
+        .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        //Resources are written with their name, like in the checkpoints
+        assert!(content.contains("\"Carbon\""), "{content}");
+        let replay = GalaxyReplay::from_jsonl(&content).unwrap();
+        let bag = replay.entries().last().unwrap();
+        assert_eq!(bag.tick, 1);
+        assert!(matches!(
+            &bag.event,
+            JournalEvent::ExplorerBag { explorer_id: 4, bag } if bag == &vec![ResourceType::Basic(BasicResourceType::Carbon)]
+        ));
+        //They explain the game, the replayed galaxy does not change
+        assert!(replay.snapshot_at(1).explorers.is_empty());
+
+        let neighbors = JournalEntry {
+            tick: 2,
+            event: JournalEvent::ExplorerNeighbors { explorer_id: 4, neighbors: vec![1, 2] },
+        };
+        let replay = GalaxyReplay::from_jsonl(&to_jsonl(&[neighbors])).unwrap();
+        assert!(matches!(
+            &replay.entries()[0].event,
+            JournalEvent::ExplorerNeighbors { explorer_id: 4, neighbors } if neighbors == &vec![1, 2]
+        ));
+    }
+
+    #[test]
+    fn test_journal_replay_rebuilds_every_tick() {
+        let entries = vec![
+            JournalEntry {
+                tick: 0,
+                event: JournalEvent::GalaxyLoaded {
+                    planets: BTreeMap::from([(1, PlanetType::Ciuc), (2, PlanetType::RustyCrab)]),
+                    links: vec![(1, 2)],
+                },
+            },
+            JournalEntry { tick: 1, event: JournalEvent::PlanetStatus { planet_id: 1, status: Status::Running } },
+            JournalEntry { tick: 1, event: JournalEvent::ExplorerSpawned { explorer_id: 7, planet_id: 1 } },
+            JournalEntry { tick: 2, event: JournalEvent::ExplorerMoved { explorer_id: 7, planet_id: 2 } },
+            JournalEntry { tick: 3, event: JournalEvent::LinkDestroyed { planet_one: 2, planet_two: 1 } },
+            JournalEntry { tick: 3, event: JournalEvent::PlanetStatus { planet_id: 2, status: Status::Dead } },
+        ];
+        let replay = GalaxyReplay::from_jsonl(&to_jsonl(&entries)).unwrap();
+        assert_eq!(replay.last_tick(), 3);
+
+        let start = replay.snapshot_at(0);
+        assert!(start.topology.are_linked(1, 2));
+        assert_eq!(start.planets[&1].status, Status::Paused);
+        assert_eq!(start.planets[&2].planet_type, Some(PlanetType::RustyCrab));
+        assert!(start.explorers.is_empty());
+
+        let middle = replay.snapshot_at(2);
+        assert_eq!(middle.planets[&1].status, Status::Running);
+        assert_eq!(middle.explorers[&7].planet_id, 2);
+        assert!(middle.version > start.version);
+
+        let end = replay.snapshot_at(3);
+        assert!(!end.topology.are_linked(1, 2));
+        assert_eq!(end.topology.destroyed_links, vec![(1, 2)]);
+        assert_eq!(end.planets[&2].status, Status::Dead);
+        //Going back gives the same state as before
+        assert_eq!(replay.snapshot_at(2), middle);
+    }
+
+    #[test]
+    fn test_journal_replay_shows_the_last_of_two_games_in_the_same_file() {
+        let path = journal_path("two_games");
+        let mut first = Journal::create(&path).unwrap();
+        let galaxy = |planet_type| JournalEvent::GalaxyLoaded {
+            planets: BTreeMap::from([(1, planet_type), (2, planet_type)]),
+            links: vec![(1, 2)],
+        };
+        first.record(0, galaxy(PlanetType::Ciuc)).unwrap();
+        first.record(4, JournalEvent::PlanetStatus { planet_id: 2, status: Status::Dead }).unwrap();
+        drop(first);
+
+        //A second run with the same journal file starts again from tick 0
+        let mut second = Journal::create(&path).unwrap();
+        second.record(0, galaxy(PlanetType::RustyCrab)).unwrap();
+        second.record(1, JournalEvent::PlanetStatus { planet_id: 1, status: Status::Running }).unwrap();
+        drop(second);
+
+        let replay = GalaxyReplay::from_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(replay.entries().iter().any(|entry| matches!(entry.event, JournalEvent::Reset)));
+        assert_eq!(replay.last_tick(), 1);
+        let end = replay.snapshot_at(4);
+        assert_eq!(end.planets[&1].planet_type, Some(PlanetType::RustyCrab));
+        assert_eq!(end.planets[&1].status, Status::Running);
+        //Planet 2 died in the first game only
+        assert_eq!(end.planets[&2].status, Status::Paused);
+    }
+
+    #[test]
+    fn test_journal_replay_reports_the_broken_line() {
+        let valid = to_jsonl(&[JournalEntry { tick: 0, event: JournalEvent::Reset }]);
+        let error = GalaxyReplay::from_jsonl(&format!("{valid}not json\n")).unwrap_err();
+        assert!(error.starts_with("line 2:"), "{error}");
+    }
+}
+
+#[cfg(test)]
+mod tests_checkpoints {
+    use super::*;
+    use crate::utils::checkpoint::{GalaxyCheckpoint, PlanetStateSummary};
+    use crate::utils::errors::OrchestratorError;
+    use crate::utils::scoring::{EndCondition, GameOverReason};
+    use common_game::components::resource::{BasicResourceType, ResourceType};
+    use std::collections::BTreeMap;
+
+    const CARBON: ResourceType = ResourceType::Basic(BasicResourceType::Carbon);
+
+    fn checkpoint_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("omc_galaxy_checkpoint_{name}_{}.json", std::process::id()))
+    }
+
+    //Only one forge can exist at a time: the checkpoints are restored in the same orchestrator, once reset
+    fn restore(mut orch: Orchestrator, checkpoint: GalaxyCheckpoint) -> Result<Orchestrator, OrchestratorError> {
+        orch.reset().unwrap();
+        orch.restore_checkpoint(checkpoint)?;
+        Ok(orch)
+    }
+
+    //Mock planets 1 - 2 - 3
+    fn mock_galaxy() -> Orchestrator {
+        let mut orch = Orchestrator::new().unwrap();
+        for planet_id in 1..=3 {
+            orch.galaxy_lookup.insert(planet_id, PlanetType::MOCK);
+        }
+        orch.initialize_galaxy_by_neighbors(BTreeMap::from([(1, vec![2]), (2, vec![1, 3]), (3, vec![2])]))
+            .unwrap();
+        orch
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip_restores_the_galaxy() {
+        let mut orch = mock_galaxy();
+        orch.spawn_explorer(5, 1).unwrap();
+        orch.advance_tick();
+        orch.remove_link(2, 3).unwrap();
+        orch.send_planet_kill(&orch.planet_channels[&3].0).unwrap();
+        orch.set_planet_status(3, Status::Dead);
+
+        let path = checkpoint_path("round_trip");
+        orch.checkpoint().unwrap().save(&path).unwrap();
+        let checkpoint = GalaxyCheckpoint::from_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(checkpoint.tick, 1);
+        assert_eq!(checkpoint.links, vec![(1, 2)]);
+        assert_eq!(checkpoint.explorers[&5].planet_id, 1);
+
+        let restored = restore(orch, checkpoint).unwrap();
+        assert_eq!(restored.current_tick, 1);
+        assert_eq!(restored.galaxy_lookup.len(), 3);
+        assert_eq!(restored.planets_status.read().unwrap().get(&1), Some(&Status::Paused));
+        assert_eq!(restored.planets_status.read().unwrap().get(&3), Some(&Status::Dead));
+        //Dead planets are not created again
+        assert!(restored.planet_channels.contains_key(&1));
+        assert!(!restored.planet_channels.contains_key(&3));
+        let gtop = restored.galaxy_topology.read().unwrap();
+        assert!(gtop.are_linked(1, 2));
+        assert!(!gtop.are_linked(2, 3));
+        drop(gtop);
+        assert!(restored.destroyed_links.contains(&(2, 3)));
+        assert_eq!(restored.explorer_positions.get(&5), Some(&1));
+        assert!(restored.is_explorer_alive(5));
+        restored.send_planet_kill_to_all().unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_dead_planets_are_not_destroyed_again() {
+        let mut orch = mock_galaxy();
+        orch.advance_tick();
+        orch.send_planet_kill(&orch.planet_channels[&3].0).unwrap();
+        orch.set_planet_status(3, Status::Dead);
+        let checkpoint = orch.checkpoint().unwrap();
+
+        let restored = restore(orch, checkpoint).unwrap();
+        assert_eq!(restored.planets_status.read().unwrap().get(&3), Some(&Status::Dead));
+        //The planet died in the saved game, not in the restored one
+        assert_eq!(restored.metrics.ticks.len(), 1);
+        assert_eq!(restored.metrics.ticks[0].tick, 1);
+        assert_eq!(restored.metrics.ticks[0].planets_destroyed, 0);
+        assert_eq!(restored.planet_death_ticks.get(&3), Some(&1));
+        restored.send_planet_kill_to_all().unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_charged_cells_are_owed_to_the_restored_planet() {
+        let orch = mock_galaxy();
+        let mut checkpoint = orch.checkpoint().unwrap();
+        checkpoint.planets.get_mut(&1).unwrap().internal_state = Some(PlanetStateSummary {
+            energy_cells: vec![true, false, true],
+            has_rocket: false,
+        });
+
+        let restored = restore(orch, checkpoint).unwrap();
+        assert_eq!(restored.pending_recharges.get(&1), Some(&2));
+        assert!(!restored.pending_recharges.contains_key(&2));
+        restored.send_planet_kill_to_all().unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_save_asks_the_planets_for_their_state() {
+        let mut orch = mock_galaxy();
+        orch.start_all().unwrap();
+
+        let path = checkpoint_path("save");
+        let checkpoint = orch.save_checkpoint(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(checkpoint.planets.values().all(|planet| planet.internal_state.is_some()));
+        assert_eq!(orch.planet_states.len(), 3);
+        assert!(orch.pending_acks.is_empty());
+        orch.send_planet_kill_to_all().unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_links_to_unknown_planets() {
+        let orch = mock_galaxy();
+        let mut checkpoint = orch.checkpoint().unwrap();
+        checkpoint.links.push((1, 9));
+        assert!(matches!(
+            restore(orch, checkpoint),
+            Err(OrchestratorError::Checkpoint { .. })
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint_saved_bags_do_not_count_in_the_restored_game() {
+        let mut orch = mock_galaxy();
+        orch.spawn_explorer(5, 1).unwrap();
+        let mut checkpoint = orch.checkpoint().unwrap();
+        checkpoint.explorers.get_mut(&5).unwrap().bag = vec![CARBON];
+
+        let restored = restore(orch, checkpoint).unwrap();
+        assert_eq!(restored.restored_bags.get(&5), Some(&vec![CARBON]));
+        //The explorer starts again empty, only its new bags are scored
+        assert!(!restored.explorer_bags.contains_key(&5));
+        let conditions = [EndCondition::ResourceCrafted { resource: CARBON }];
+        assert!(restored.check_end_conditions(&conditions).is_none());
+        assert_eq!(restored.scoreboard(GameOverReason::AllExplorersDead).explorers[&5].score, 0);
+        restored.send_planet_kill_to_all().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests_game_over {
+    use super::*;
+    use crate::utils::scoring::{EndCondition, ExplorerScore, GameOverReason, resource_weight};
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+    use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+
+    const WATER: ResourceType = ResourceType::Complex(ComplexResourceType::Water);
+    const OXYGEN: ResourceType = ResourceType::Basic(BasicResourceType::Oxygen);
+
+    #[test]
+    fn test_game_over_when_every_planet_is_dead() {
+        let mut orch = Orchestrator::new().unwrap();
+        let conditions = [EndCondition::AllPlanetsDead, EndCondition::AllExplorersDead];
+        //An empty galaxy is not over
+        assert!(orch.check_end_conditions(&conditions).is_none());
+
+        orch.set_planet_status(1, Status::Dead);
+        orch.set_planet_status(2, Status::Running);
+        assert!(orch.check_end_conditions(&conditions).is_none());
+
+        orch.set_planet_status(2, Status::Crashed);
+        assert!(matches!(
+            orch.check_end_conditions(&conditions),
+            Some(GameOverReason::AllPlanetsDead)
+        ));
+    }
+
+    #[test]
+    fn test_game_over_on_tick_limit_and_crafted_resource() {
+        let mut orch = Orchestrator::new().unwrap();
+        let conditions = [
+            EndCondition::TickLimit { tick: 3 },
+            EndCondition::ResourceCrafted { resource: WATER },
+        ];
+        orch.explorer_bags.insert(4, vec![OXYGEN]);
+        assert!(orch.check_end_conditions(&conditions).is_none());
+
+        orch.explorer_bags.insert(5, vec![OXYGEN, WATER]);
+        assert!(matches!(
+            orch.check_end_conditions(&conditions),
+            Some(GameOverReason::ResourceCrafted { explorer_id: 5, .. })
+        ));
+
+        orch.explorer_bags.clear();
+        for _ in 0..3 {
+            orch.advance_tick();
+        }
+        assert!(matches!(
+            orch.check_end_conditions(&conditions),
+            Some(GameOverReason::TickLimit { tick: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_game_over_scoreboard_survival_and_scores() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.set_planet_status(1, Status::Paused);
+        orch.planet_birth_ticks.insert(1, 2);
+        orch.set_planet_status(2, Status::Paused);
+        orch.planet_birth_ticks.insert(2, 0);
+        orch.current_tick = 7;
+        orch.set_planet_status(1, Status::Dead);
+        orch.current_tick = 10;
+        orch.set_explorer_status(8, Status::Running);
+        orch.set_explorer_status(9, Status::Dead);
+        orch.explorer_bags.insert(8, vec![OXYGEN]);
+        orch.explorer_bags.insert(9, vec![WATER, OXYGEN]);
+
+        let scoreboard = orch.scoreboard(GameOverReason::AllExplorersDead);
+        assert_eq!(scoreboard.tick, 10);
+        assert_eq!(scoreboard.planets[&1].survived_ticks, 5);
+        assert_eq!(scoreboard.planets[&2].survived_ticks, 10);
+        assert_eq!(scoreboard.explorers[&8].score, 1);
+        assert_eq!(scoreboard.explorers[&9].score, resource_weight(&WATER) + resource_weight(&OXYGEN));
+        assert_eq!(scoreboard.winner(), Some(9));
+        //The scoreboard reaches the UI as JSON
+        let json = serde_json::to_string(&scoreboard).unwrap();
+        assert!(json.contains("\"Water\""));
+    }
+
+    #[test]
+    fn test_game_over_weights_grow_with_the_recipe() {
+        assert_eq!(ExplorerScore::new(Status::Running, Vec::new()).score, 0);
+        assert!(resource_weight(&OXYGEN) < resource_weight(&WATER));
+        assert!(
+            resource_weight(&WATER) < resource_weight(&ResourceType::Complex(ComplexResourceType::AIPartner))
+        );
+    }
+
+    #[test]
+    fn test_game_over_polled_bags_are_not_sent_to_the_ui() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 3,
+            bag_content: vec![WATER],
+        })
+        .unwrap();
+        assert_eq!(orch.explorer_bags[&3].len(), 1);
+        assert!(orch.ui_events.is_empty());
+
+        orch.ui_bag_requests.insert(3);
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 3,
+            bag_content: vec![WATER, OXYGEN],
+        })
+        .unwrap();
+        assert_eq!(orch.ui_events.len(), 1);
+        assert!(orch.ui_bag_requests.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_metrics {
+    use super::*;
+    use crate::utils::metrics::{GameMetrics, MetricsFormat};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_metrics_count_every_tick() {
+        let mut orch = Orchestrator::new().unwrap();
+        orch.add_planet(1, PlanetType::MOCK).unwrap();
+        orch.start_all().unwrap();
+
+        orch.send_sunray_to(1).unwrap();
+        let ack = orch
+            .recevier_orch_planet
+            .recv_timeout(Duration::from_secs(2))
+            .expect("the mock planet did not answer");
+        orch.handle_planet_message(ack).unwrap();
+        orch.advance_tick();
+        orch.set_planet_status(1, Status::Dead);
+        orch.advance_tick();
+
+        let ticks = &orch.metrics.ticks;
+        assert_eq!(ticks.len(), 3);
+        assert_eq!(ticks[0].sunrays_sent, 1);
+        assert_eq!(ticks[0].live_planets, 1);
+        assert!(ticks[0].latency_ms.contains_key(&1));
+        assert_eq!(ticks[1].planets_destroyed, 1);
+        assert_eq!(ticks[1].live_planets, 0);
+        assert_eq!(ticks[2].tick, 2);
+        //The start and the sunray were both acknowledged
+        assert_eq!(orch.metrics.planet_latency[&1].acks, 2);
+        orch.send_planet_kill(&orch.planet_channels[&1].0).unwrap();
+    }
+
+    #[test]
+    fn test_metrics_csv_has_a_row_per_tick() {
+        let mut metrics = GameMetrics::new();
+        let sent = Instant::now();
+        metrics.request_sent(4, sent);
+        metrics.asteroid_sent();
+        metrics.rocket_fired();
+        metrics.ack_received(4, sent + Duration::from_millis(30));
+        metrics.end_tick(1, 1);
+        metrics.explorer_action();
+
+        let csv = metrics.export(MetricsFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "tick,live_planets,sunrays_sent,asteroids_sent,rockets_fired,planets_destroyed,explorer_actions,latency_ms_planet_4"
+        );
+        assert_eq!(lines[1], "0,1,0,1,1,0,0,30.000");
+        assert_eq!(lines[2], "1,1,0,0,0,0,1,");
+        assert!((metrics.planet_latency[&4].max_ms - 30.0).abs() < 1e-9);
+
+        let json: serde_json::Value = serde_json::from_str(&metrics.export(MetricsFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["ticks"].as_array().unwrap().len(), 2);
+        assert_eq!(json["planet_latency"]["4"]["acks"], 1);
+    }
+
+    #[test]
+    fn test_metrics_explorer_actions_skip_the_orchestrator_polls() {
+        use common_game::protocols::orchestrator_explorer::ExplorerToOrchestrator;
+
+        let mut orch = Orchestrator::new().unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::StartExplorerAIResult { explorer_id: 3 })
+            .unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 3,
+            bag_content: Vec::new(),
+        })
+        .unwrap();
+        orch.handle_explorer_message(ExplorerToOrchestrator::KillExplorerResult { explorer_id: 3 })
+            .unwrap();
+        assert_eq!(orch.metrics.ticks[0].explorer_actions, 0);
+
+        //A bag asked by the UI is an action
+        orch.ui_bag_requests.insert(3);
+        orch.handle_explorer_message(ExplorerToOrchestrator::BagContentResponse {
+            explorer_id: 3,
+            bag_content: Vec::new(),
+        })
+        .unwrap();
+        assert_eq!(orch.metrics.ticks[0].explorer_actions, 1);
+    }
+
+    #[test]
+    fn test_metrics_file_format_follows_the_extension() {
+        assert_eq!(MetricsFormat::from_path("run.CSV".as_ref()), MetricsFormat::Csv);
+        assert_eq!(MetricsFormat::from_path("run.json".as_ref()), MetricsFormat::Json);
+        assert_eq!(MetricsFormat::from_path("run".as_ref()), MetricsFormat::Json);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,7 +2139,6 @@ mod tests {
     // Testing one of every single planet in the registry simultaneously.
     mod planet_integration {
         use super::*;
-        use strum::IntoEnumIterator;
 
         #[test]
         fn test_orchestrator_integration_all_planet_types_behavior() {
@@ -233,7 +2146,7 @@ mod tests {
             let mut id_counter = 0;
 
             // Add one of every planet type
-            for p_type in PlanetType::iter() {
+            for p_type in PlanetType::BUILTIN {
                 orch.add_planet(id_counter, p_type).unwrap();
                 id_counter += 1;
             }
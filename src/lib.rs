@@ -2,7 +2,25 @@ mod components;
 pub mod settings;
 mod utils;
 pub mod messages;
+pub mod bridge;
 
 pub use components::Game;
-pub use components::game_loop::run_with_ui;
-pub use utils::{GalaxyTopology, PlanetStatus, ExplorerStatus};
\ No newline at end of file
+pub use components::game_loop::{run_from_checkpoint, run_replay, run_with_ui, run_with_ui_definition};
+pub use utils::{ExplorerSnapshot, GalaxySnapshot, PlanetSnapshot, Status, TopologySnapshot};
+pub use utils::registry::PlanetType;
+pub use utils::galaxy_file::{ExplorerSpawn, GalaxyDefinition, GalaxyMetadata, PlanetDefinition};
+pub use utils::galaxy_validation::{Diagnostic, Severity};
+pub use utils::export::ExportFormat;
+pub use utils::generator::{GalaxyGenerator, GraphModel, TypeChoice, TypeMix};
+pub use utils::registry::{ClassRules, EnergyCells, Limit, PlanetCapabilities, PlanetClass, register_planet};
+pub use utils::types::{PlanetConfig, PlanetFactory};
+pub use utils::topology_analysis::{PlanetGraph, TopologyAnalysis};
+pub use utils::galaxy_graph::GalaxyGraph;
+pub use utils::errors::{Actor, OrchestratorError};
+pub use utils::logging::LogSink;
+pub use utils::journal::{GalaxyReplay, JournalEntry, JournalEvent};
+pub use utils::checkpoint::{ExplorerCheckpoint, GalaxyCheckpoint, PlanetCheckpoint, PlanetStateSummary};
+pub use utils::metrics::{GameMetrics, LatencyStats, MetricsFormat, TickMetrics};
+pub use utils::scoring::{EndCondition, ExplorerScore, GameOverReason, PlanetScore, Scoreboard, resource_weight};
+#[cfg(feature = "mock-planets")]
+pub use utils::mock_planets::MockBehaviour;
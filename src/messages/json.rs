@@ -0,0 +1,101 @@
+//! Line-delimited JSON encoding of the UI messages
+//!
+//! Every message is a single line of JSON using the default serde
+//! representation of the enums, for example:
+//!
+//! ```text
+//! "StartGame"
+//! {"SendAsteroid":{"planet_id":3}}
+//! {"CommandResult":{"Ok":{"SunraySent":{"planet_id":3}}}}
+//! ```
+//!
+//! Resources inside a bag are encoded with their name (e.g. `"Hydrogen"`, `"AIPartner"`).
+
+use crate::messages::{GameToUi, UiToGame};
+
+pub fn encode_ui_command(command: &UiToGame) -> Result<String, String> {
+    serde_json::to_string(command).map_err(|e| format!("Unable to encode UI command: {e}"))
+}
+
+pub fn decode_ui_command(line: &str) -> Result<UiToGame, String> {
+    serde_json::from_str(line.trim()).map_err(|e| format!("Invalid UI command '{}': {e}", line.trim()))
+}
+
+pub fn encode_game_message(msg: &GameToUi) -> Result<String, String> {
+    serde_json::to_string(msg).map_err(|e| format!("Unable to encode game message: {e}"))
+}
+
+pub fn decode_game_message(line: &str) -> Result<GameToUi, String> {
+    serde_json::from_str(line.trim()).map_err(|e| format!("Invalid game message '{}': {e}", line.trim()))
+}
+
+/// Serde helpers for `BagType`
+///
+/// `ResourceType` comes from `common_game` and does not implement serde,
+/// so every resource is written as the name of its basic or complex type
+pub(crate) mod resource_types {
+    use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn resource_name(resource: &ResourceType) -> &'static str {
+        match resource {
+            ResourceType::Basic(BasicResourceType::Hydrogen) => "Hydrogen",
+            ResourceType::Basic(BasicResourceType::Oxygen) => "Oxygen",
+            ResourceType::Basic(BasicResourceType::Carbon) => "Carbon",
+            ResourceType::Basic(BasicResourceType::Silicon) => "Silicon",
+            ResourceType::Complex(ComplexResourceType::Diamond) => "Diamond",
+            ResourceType::Complex(ComplexResourceType::Water) => "Water",
+            ResourceType::Complex(ComplexResourceType::Life) => "Life",
+            ResourceType::Complex(ComplexResourceType::Robot) => "Robot",
+            ResourceType::Complex(ComplexResourceType::Dolphin) => "Dolphin",
+            ResourceType::Complex(ComplexResourceType::AIPartner) => "AIPartner",
+        }
+    }
+
+    pub(crate) fn resource_from_name(name: &str) -> Option<ResourceType> {
+        let resource = match name {
+            "Hydrogen" => ResourceType::Basic(BasicResourceType::Hydrogen),
+            "Oxygen" => ResourceType::Basic(BasicResourceType::Oxygen),
+            "Carbon" => ResourceType::Basic(BasicResourceType::Carbon),
+            "Silicon" => ResourceType::Basic(BasicResourceType::Silicon),
+            "Diamond" => ResourceType::Complex(ComplexResourceType::Diamond),
+            "Water" => ResourceType::Complex(ComplexResourceType::Water),
+            "Life" => ResourceType::Complex(ComplexResourceType::Life),
+            "Robot" => ResourceType::Complex(ComplexResourceType::Robot),
+            "Dolphin" => ResourceType::Complex(ComplexResourceType::Dolphin),
+            "AIPartner" => ResourceType::Complex(ComplexResourceType::AIPartner),
+            _ => return None,
+        };
+        Some(resource)
+    }
+
+    pub(crate) fn serialize<S: Serializer>(bag: &[ResourceType], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(bag.iter().map(resource_name))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ResourceType>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|name| resource_from_name(name).ok_or_else(|| D::Error::custom(format!("unknown resource '{name}'"))))
+            .collect()
+    }
+}
+
+/// Serde helpers for a single `ResourceType`, written with its name like in `resource_types`
+pub(crate) mod resource_type {
+    use common_game::components::resource::ResourceType;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::resource_types::{resource_from_name, resource_name};
+
+    pub(crate) fn serialize<S: Serializer>(resource: &ResourceType, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(resource_name(resource))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ResourceType, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        resource_from_name(&name).ok_or_else(|| D::Error::custom(format!("unknown resource '{name}'")))
+    }
+}
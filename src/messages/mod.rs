@@ -1,4 +1,5 @@
 pub mod ui_messages;
+pub mod json;
 
 
-pub use ui_messages::*;
\ No newline at end of file
+pub use ui_messages::*;
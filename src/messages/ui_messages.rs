@@ -1,20 +1,164 @@
 use common_game::components::planet::Planet;
+use serde::{Deserialize, Serialize};
 
-use crate::utils::{ExplorerStatus, GalaxyTopology, PlanetStatus};
+use crate::components::explorer::BagType;
+use crate::messages::json::resource_types;
+use crate::utils::GalaxySnapshot;
+use crate::utils::errors::OrchestratorError;
+use crate::utils::export::ExportFormat;
+use crate::utils::metrics::MetricsFormat;
+use crate::utils::registry::PlanetType;
+use crate::utils::scoring::Scoreboard;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum GameToUi{
     GameStatusUpdate{
-        galaxy_topology: GalaxyTopology,    
-        planets_status: PlanetStatus,
-        explorer_status: ExplorerStatus,
-    }
+        snapshot: GalaxySnapshot,
+    },
+    /// Answer to one of the manual interaction commands of `UiToGame`
+    CommandResult(Result<CommandReply, CommandFailure>),
+    /// A planet joined the galaxy while the game was going on
+    PlanetAdded{
+        planet_id: u32,
+        planet_type: PlanetType,
+        neighbors: Vec<u32>,
+    },
+    /// A planet left the galaxy together with its links
+    PlanetRemoved{
+        planet_id: u32,
+    },
+    /// A planet did not acknowledge a request in time, `killed` if it was also isolated from the galaxy
+    PlanetUnresponsive{
+        planet_id: u32,
+        killed: bool,
+    },
+    /// The thread of a planet panicked or ended on its own, `reason` is the panic message or the error
+    PlanetCrashed{
+        planet_id: u32,
+        reason: String,
+    },
+    /// One of the end conditions was met, the game stops right after this message
+    GameOver{
+        scoreboard: Scoreboard,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum UiToGame{
     StartGame,
     StopGame,
+    //Advances the game by a single tick, only while it is paused
+    StepGame,
     ResetGame,
     EndGame,
+    //Only for a replay of a journal, jumps to the state of the galaxy at the end of the tick
+    SeekTick{
+        tick: u64,
+    },
+
+    //Manual interaction with the simulation, every command is answered with a GameToUi::CommandResult
+    SpawnExplorer{
+        explorer_id: u32,
+        planet_id: u32,
+    },
+    KillExplorer{
+        explorer_id: u32,
+    },
+    MoveExplorer{
+        explorer_id: u32,
+        planet_id: u32,
+    },
+    SendSunray{
+        planet_id: u32,
+    },
+    SendAsteroid{
+        planet_id: u32,
+    },
+    //The new planet is started right away if the game is running
+    AddPlanet{
+        planet_id: u32,
+        planet_type: PlanetType,
+        neighbors: Vec<u32>,
+    },
+    //Fails if some explorer is still on the planet
+    RemovePlanet{
+        planet_id: u32,
+    },
+    RemoveLink{
+        planet_one: u32,
+        planet_two: u32,
+    },
+    BagDump{
+        explorer_id: u32,
+    },
+    //Dumps the current topology, answered with CommandReply::TopologyExported
+    ExportTopology{
+        format: ExportFormat,
+    },
+    //Writes a checkpoint of the galaxy to the file, see run_from_checkpoint
+    SaveCheckpoint{
+        path: String,
+    },
+    //Metrics collected so far, answered with CommandReply::MetricsExported
+    ExportMetrics{
+        format: MetricsFormat,
+    },
+}
+
+/// Successful outcome of a manual interaction command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CommandReply{
+    ExplorerSpawned{
+        explorer_id: u32,
+        planet_id: u32,
+    },
+    ExplorerKilled{
+        explorer_id: u32,
+    },
+    ExplorerMoved{
+        explorer_id: u32,
+        planet_id: u32,
+    },
+    SunraySent{
+        planet_id: u32,
+    },
+    AsteroidSent{
+        planet_id: u32,
+    },
+    PlanetAdded{
+        planet_id: u32,
+    },
+    PlanetRemoved{
+        planet_id: u32,
+    },
+    LinkRemoved{
+        planet_one: u32,
+        planet_two: u32,
+    },
+    BagContent{
+        explorer_id: u32,
+        #[serde(with = "resource_types")]
+        bag: BagType,
+    },
+    TopologyExported{
+        format: ExportFormat,
+        content: String,
+    },
+    CheckpointSaved{
+        path: String,
+        tick: u64,
+    },
+    MetricsExported{
+        format: MetricsFormat,
+        content: String,
+    },
+}
+
+/// A manual interaction command that could not be executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandFailure{
+    pub command: UiToGame,
+    /// Text of `error`, for the logs
+    pub reason: String,
+    pub error: OrchestratorError,
 }
@@ -1,4 +1,12 @@
-use std::sync::{LazyLock, RwLock};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::Duration;
+
+use common_game::logging::Channel;
+
+use crate::utils::logging::{self, LogSink, LogWriter};
+use crate::utils::scoring::EndCondition;
 
 // Viene inizializzato a "".to_string() AUTOMATICAMENTE al primo utilizzo
 static SUNRAY_ASTEROID_SEQUENCE: LazyLock<RwLock<String>> =
@@ -18,3 +26,99 @@ pub fn pop_sunray_asteroid_sequence() -> Option<char> {
     let mut seq = SUNRAY_ASTEROID_SEQUENCE.write().unwrap();
     seq.pop()
 }
+
+// Se attivo, l'inizializzazione della galassia avvisa quando ci sono pianeti irraggiungibili
+static WARN_DISCONNECTED_GALAXY: AtomicBool = AtomicBool::new(false);
+
+pub fn get_warn_disconnected_galaxy() -> bool {
+    WARN_DISCONNECTED_GALAXY.load(Ordering::Relaxed)
+}
+
+pub fn set_warn_disconnected_galaxy(enabled: bool) {
+    WARN_DISCONNECTED_GALAXY.store(enabled, Ordering::Relaxed);
+}
+
+// Tempo massimo (in millisecondi) entro cui un pianeta deve rispondere a una richiesta dell'orchestratore
+static PLANET_ACK_TIMEOUT_MS: AtomicU64 = AtomicU64::new(2000);
+
+pub fn get_planet_ack_timeout() -> Duration {
+    Duration::from_millis(PLANET_ACK_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+pub fn set_planet_ack_timeout(timeout: Duration) {
+    PLANET_ACK_TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+// Se attivo, i pianeti che non rispondono in tempo vengono uccisi e scollegati dalla galassia
+static KILL_UNRESPONSIVE_PLANETS: AtomicBool = AtomicBool::new(false);
+
+pub fn get_kill_unresponsive_planets() -> bool {
+    KILL_UNRESPONSIVE_PLANETS.load(Ordering::Relaxed)
+}
+
+pub fn set_kill_unresponsive_planets(enabled: bool) {
+    KILL_UNRESPONSIVE_PLANETS.store(enabled, Ordering::Relaxed);
+}
+
+// Livello del log strutturato: vengono scritti solo gli eventi dei canali uguali o più importanti
+// Con la feature debug-prints si parte da Debug, altrimenti solo warning ed errori
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(if cfg!(feature = "debug-prints") { 3 } else { 1 });
+
+pub fn get_log_level() -> Channel {
+    logging::channel_of_rank(LOG_LEVEL.load(Ordering::Relaxed))
+}
+
+pub fn set_log_level(level: Channel) {
+    LOG_LEVEL.store(logging::channel_rank(&level), Ordering::Relaxed);
+}
+
+pub fn log_enabled(channel: &Channel) -> bool {
+    logging::channel_rank(channel) <= LOG_LEVEL.load(Ordering::Relaxed)
+}
+
+// Destinazione del log strutturato, stderr di default
+static LOG_WRITER: LazyLock<Mutex<LogWriter>> = LazyLock::new(|| Mutex::new(LogWriter::Stderr));
+
+pub fn set_log_sink(sink: LogSink) -> Result<(), String> {
+    let writer = LogWriter::open(sink)?;
+    *LOG_WRITER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = writer;
+    Ok(())
+}
+
+pub(crate) fn with_log_writer(write: impl FnOnce(&mut LogWriter)) {
+    write(&mut LOG_WRITER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+}
+
+// File in cui l'orchestratore registra il diario della partita, nessuno di default
+static JOURNAL_FILE: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(|| RwLock::new(None));
+
+pub fn get_journal_file() -> Option<PathBuf> {
+    JOURNAL_FILE.read().unwrap().clone()
+}
+
+pub fn set_journal_file(path: Option<PathBuf>) {
+    *JOURNAL_FILE.write().unwrap() = path;
+}
+
+// Condizioni di fine partita, la partita termina appena una di queste è soddisfatta
+static END_CONDITIONS: LazyLock<RwLock<Vec<EndCondition>>> =
+    LazyLock::new(|| RwLock::new(vec![EndCondition::AllPlanetsDead]));
+
+pub fn get_end_conditions() -> Vec<EndCondition> {
+    END_CONDITIONS.read().unwrap().clone()
+}
+
+pub fn set_end_conditions(conditions: Vec<EndCondition>) {
+    *END_CONDITIONS.write().unwrap() = conditions;
+}
+
+// File in cui vengono salvate le metriche a fine partita (CSV se l'estensione è .csv, altrimenti JSON)
+static METRICS_FILE: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(|| RwLock::new(None));
+
+pub fn get_metrics_file() -> Option<PathBuf> {
+    METRICS_FILE.read().unwrap().clone()
+}
+
+pub fn set_metrics_file(path: Option<PathBuf>) {
+    *METRICS_FILE.write().unwrap() = path;
+}
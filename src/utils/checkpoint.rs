@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use common_game::components::planet::DummyPlanetState;
+use serde::{Deserialize, Serialize};
+
+use crate::components::explorer::BagType;
+use crate::messages::json::resource_types;
+use crate::utils::registry::PlanetType;
+use crate::utils::types::PlanetConfig;
+use crate::utils::Status;
+
+/// Everything needed to start a galaxy again from where it was, see `Orchestrator::from_checkpoint`
+///
+/// Planets and explorers are started again from scratch: only the state their protocols
+/// can rebuild is restored, the rest (e.g. the bags of the explorers) is kept for reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalaxyCheckpoint {
+    pub tick: u64,
+    pub planets: BTreeMap<u32, PlanetCheckpoint>,
+    /// Links still existing, as (lower id, higher id)
+    pub links: Vec<(u32, u32)>,
+    #[serde(default)]
+    pub destroyed_links: Vec<(u32, u32)>,
+    #[serde(default)]
+    pub explorers: BTreeMap<u32, ExplorerCheckpoint>,
+    /// Events still to be sent, see `settings::set_sunray_asteroid_sequence`
+    #[serde(default)]
+    pub sunray_asteroid_sequence: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetCheckpoint {
+    pub planet_type: PlanetType,
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub config: PlanetConfig,
+    /// Last internal state the planet answered with, none if it never did
+    #[serde(default)]
+    pub internal_state: Option<PlanetStateSummary>,
+}
+
+/// The part of the internal state of a planet the orchestrator can see
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanetStateSummary {
+    /// Charge of every energy cell
+    pub energy_cells: Vec<bool>,
+    pub has_rocket: bool,
+}
+
+impl PlanetStateSummary {
+    pub fn charged_cells(&self) -> usize {
+        self.energy_cells.iter().filter(|charged| **charged).count()
+    }
+}
+
+impl From<&DummyPlanetState> for PlanetStateSummary {
+    fn from(state: &DummyPlanetState) -> Self {
+        Self {
+            energy_cells: state.energy_cells.clone(),
+            has_rocket: state.has_rocket,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerCheckpoint {
+    pub status: Status,
+    pub planet_id: u32,
+    /// Last content of the bag, resources cannot be recreated so a restored explorer starts empty
+    #[serde(default, with = "resource_types")]
+    pub bag: BagType,
+}
+
+impl GalaxyCheckpoint {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read the checkpoint {}: {e}", path.display()))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid checkpoint {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let content =
+            serde_json::to_string_pretty(self).map_err(|e| format!("Unable to encode the checkpoint: {e}"))?;
+        std::fs::write(path, content).map_err(|e| format!("Unable to write the checkpoint {}: {e}", path.display()))
+    }
+}
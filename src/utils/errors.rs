@@ -0,0 +1,116 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::galaxy_validation::Diagnostic;
+
+/// Actor on the other end of a channel of the orchestrator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Actor {
+    Planet(u32),
+    Explorer(u32),
+    //The shared channels every planet or explorer sends on
+    AllPlanets,
+    AllExplorers,
+    //The channels to the front end the game is played with
+    Ui,
+}
+
+impl fmt::Display for Actor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Actor::Planet(planet_id) => write!(f, "planet {planet_id}"),
+            Actor::Explorer(explorer_id) => write!(f, "explorer {explorer_id}"),
+            Actor::AllPlanets => write!(f, "the planets"),
+            Actor::AllExplorers => write!(f, "the explorers"),
+            Actor::Ui => write!(f, "the UI"),
+        }
+    }
+}
+
+/// Everything that can go wrong in the orchestrator
+///
+/// The UI gets it in every `CommandFailure`, so it can react to the kind of error
+/// instead of matching the text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OrchestratorError {
+    /// The other end of the channel is gone, e.g. the thread of a crashed planet
+    ChannelDisconnected { actor: Actor },
+    UnknownPlanet { planet_id: u32 },
+    UnknownExplorer { explorer_id: u32 },
+    /// The planet exists but it is dead or crashed
+    PlanetNotAlive { planet_id: u32 },
+    ExplorerNotAlive { explorer_id: u32 },
+    /// A thread panicked while holding one of the shared locks
+    LockPoisoned { lock: String },
+    /// The galaxy file cannot be read or is invalid, every problem with its line when it is known
+    InitFile { problems: Vec<Diagnostic> },
+    /// The factory of a planet refused to build it, e.g. because of its config
+    PlanetCreation { planet_id: u32, reason: String },
+    /// The planets did not answer in time
+    Timeout { planet_ids: Vec<u32> },
+    /// An actor sent a message that does not fit the protocol, e.g. with an unknown id
+    ///
+    /// Late answers of planets removed, crashed or killed meanwhile are expected, they are logged and dropped
+    ProtocolViolation { actor: Actor, message: String },
+    /// The request is not valid in the current galaxy, e.g. a planet that already exists
+    InvalidCommand { reason: String },
+    /// The forge of sunrays and asteroids cannot be created
+    Forge { reason: String },
+    /// The journal file of the game cannot be opened
+    Journal { reason: String },
+    /// A checkpoint cannot be written, read or restored
+    Checkpoint { reason: String },
+    /// The UI sent EndGame, the game loop stops with this error
+    GameEnded,
+    /// The thread of the game panicked
+    GameCrashed { reason: String },
+    /// The connection to an external front end failed, e.g. the socket of the JSON bridge
+    Transport { reason: String },
+}
+
+impl OrchestratorError {
+    pub(crate) fn lock_poisoned(lock: &str) -> Self {
+        OrchestratorError::LockPoisoned { lock: lock.to_string() }
+    }
+
+    pub(crate) fn invalid(reason: impl Into<String>) -> Self {
+        OrchestratorError::InvalidCommand { reason: reason.into() }
+    }
+
+    pub(crate) fn disconnected(actor: Actor) -> Self {
+        OrchestratorError::ChannelDisconnected { actor }
+    }
+}
+
+impl fmt::Display for OrchestratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrchestratorError::ChannelDisconnected { actor } => write!(f, "The channel of {actor} is closed"),
+            OrchestratorError::UnknownPlanet { planet_id } => write!(f, "Planet {planet_id} does not exist"),
+            OrchestratorError::UnknownExplorer { explorer_id } => write!(f, "Explorer {explorer_id} does not exist"),
+            OrchestratorError::PlanetNotAlive { planet_id } => write!(f, "Planet {planet_id} is dead"),
+            OrchestratorError::ExplorerNotAlive { explorer_id } => write!(f, "Explorer {explorer_id} is dead"),
+            OrchestratorError::LockPoisoned { lock } => write!(f, "Unable to lock the {lock}"),
+            OrchestratorError::InitFile { problems } => {
+                write!(f, "Invalid galaxy file:")?;
+                for problem in problems {
+                    write!(f, "\n{problem}")?;
+                }
+                Ok(())
+            }
+            OrchestratorError::PlanetCreation { planet_id, reason } => write!(f, "Planet {planet_id}: {reason}"),
+            OrchestratorError::Timeout { planet_ids } => write!(f, "The planets {planet_ids:?} did not answer in time"),
+            OrchestratorError::ProtocolViolation { actor, message } => write!(f, "Unexpected message from {actor}: {message}"),
+            OrchestratorError::InvalidCommand { reason } => write!(f, "{reason}"),
+            OrchestratorError::Forge { reason } => write!(f, "Unable to create the forge: {reason}"),
+            OrchestratorError::Journal { reason } => write!(f, "{reason}"),
+            OrchestratorError::Checkpoint { reason } => write!(f, "Checkpoint: {reason}"),
+            OrchestratorError::GameEnded => write!(f, "The game is terminated"),
+            OrchestratorError::GameCrashed { reason } => write!(f, "The game thread panicked: {reason}"),
+            OrchestratorError::Transport { reason } => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for OrchestratorError {}
@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::utils::{GalaxySnapshot, Status};
+
+/// Formats in which the galaxy topology can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// Graphviz undirected graph, render it with `dot -Tsvg galaxy.dot -o galaxy.svg`
+    Dot,
+    /// Lists of planets and links
+    Json,
+}
+
+impl GalaxySnapshot {
+    /// Exports planets, types, status and links of the galaxy in the given format
+    pub fn export_topology(&self, format: ExportFormat) -> Result<String, String> {
+        match format {
+            ExportFormat::Dot => Ok(self.topology_to_dot()),
+            ExportFormat::Json => self.topology_to_json(),
+        }
+    }
+
+    /// Graphviz version of the topology: dead planets are greyed out, destroyed links are dashed
+    pub fn topology_to_dot(&self) -> String {
+        let mut dot = String::from("graph galaxy {\n    node [style=filled, shape=circle];\n");
+
+        for id in &self.topology.planet_ids {
+            let planet = self.planets.get(id);
+            let planet_type = planet
+                .and_then(|p| p.planet_type)
+                .map(|typ| typ.to_string())
+                .unwrap_or("-".to_string());
+            let (fill, font) = match planet.map(|p| &p.status) {
+                Some(Status::Running) => ("palegreen", "black"),
+                Some(Status::Paused) => ("khaki", "black"),
+                Some(Status::Unresponsive) => ("salmon", "black"),
+                Some(Status::Crashed) => ("mistyrose", "red3"),
+                Some(Status::Dead) | None => ("grey85", "grey50"),
+            };
+            let _ = writeln!(
+                dot,
+                "    {id} [label=\"{id}\\n{planet_type}\", fillcolor={fill}, fontcolor={font}];"
+            );
+        }
+
+        for (one, two) in self.live_links() {
+            let dead = !self.is_planet_alive(one) || !self.is_planet_alive(two);
+            let style = if dead { " [color=grey70]" } else { "" };
+            let _ = writeln!(dot, "    {one} -- {two}{style};");
+        }
+        for (one, two) in &self.topology.destroyed_links {
+            let _ = writeln!(dot, "    {one} -- {two} [style=dashed, color=grey50];");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// JSON version of the topology: `{"tick", "planets": [{id, type, status}], "links": [{from, to, destroyed}]}`
+    pub fn topology_to_json(&self) -> Result<String, String> {
+        let planets: Vec<_> = self
+            .topology
+            .planet_ids
+            .iter()
+            .map(|id| {
+                let planet = self.planets.get(id);
+                json!({
+                    "id": id,
+                    "type": planet.and_then(|p| p.planet_type),
+                    "status": planet.map(|p| &p.status),
+                })
+            })
+            .collect();
+
+        let links: Vec<_> = self
+            .live_links()
+            .into_iter()
+            .map(|link| (link, false))
+            .chain(self.topology.destroyed_links.iter().map(|link| (*link, true)))
+            .map(|((one, two), destroyed)| json!({ "from": one, "to": two, "destroyed": destroyed }))
+            .collect();
+
+        serde_json::to_string_pretty(&json!({
+            "tick": self.tick,
+            "planets": planets,
+            "links": links,
+        }))
+        .map_err(|e| format!("Unable to export the topology as JSON: {e}"))
+    }
+
+    //Every link of the adjacency matrix once, as (lower id, higher id)
+    fn live_links(&self) -> Vec<(u32, u32)> {
+        let ids = &self.topology.planet_ids;
+        let mut links = Vec::new();
+        for (row, connections) in self.topology.adjacency.iter().enumerate() {
+            for (col, connected) in connections.iter().enumerate().skip(row + 1) {
+                if *connected {
+                    links.push((ids[row].min(ids[col]), ids[row].max(ids[col])));
+                }
+            }
+        }
+        links
+    }
+
+    fn is_planet_alive(&self, planet_id: u32) -> bool {
+        matches!(self.planets.get(&planet_id), Some(p) if !p.status.is_dead())
+    }
+}
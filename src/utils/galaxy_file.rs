@@ -0,0 +1,276 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::galaxy_validation::Diagnostic;
+use crate::utils::registry::PlanetType;
+
+/// Description of a galaxy, read from an init file
+///
+/// It can be written as TOML or JSON, the old CSV format
+/// (`planet_id, type_id, neighbors...`) is converted to it as well.
+///
+/// TOML example:
+/// ```toml
+/// events = "SSAS"
+///
+/// [metadata]
+/// name = "Small ring"
+///
+/// [[planets]]
+/// id = 0
+/// type = "Ciuc"
+/// neighbors = [1]
+///
+/// [[planets]]
+/// id = 1
+/// type = "houston_we_have_a_borrow"
+/// config = { rocket_strategy = "default" }
+///
+/// [[explorers]]
+/// id = 0
+/// planet_id = 1
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GalaxyDefinition {
+    #[serde(default)]
+    pub metadata: GalaxyMetadata,
+    pub planets: Vec<PlanetDefinition>,
+    #[serde(default)]
+    pub explorers: Vec<ExplorerSpawn>,
+    /// Initial sunray/asteroid sequence, same format of `settings::set_sunray_asteroid_sequence`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GalaxyMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanetDefinition {
+    pub id: u32,
+    /// Name of the planet type, see `PlanetType::from_name`
+    #[serde(rename = "type")]
+    pub planet_type: String,
+    #[serde(default)]
+    pub neighbors: Vec<u32>,
+    /// Parameters given to the planet factory, their meaning depends on the planet type
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub config: serde_json::Value,
+    /// Line of the init file where the planet is defined, used by the diagnostics
+    #[serde(skip)]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorerSpawn {
+    pub id: u32,
+    pub planet_id: u32,
+    /// Line of the init file where the explorer is defined, used by the diagnostics
+    #[serde(skip)]
+    pub line: Option<usize>,
+}
+
+impl GalaxyDefinition {
+    /// Reads a galaxy file, the format is chosen by the extension:
+    /// `.toml`, `.json`, anything else is parsed as CSV
+    ///
+    /// A file that cannot be parsed gives a diagnostic with the line of the problem
+    pub fn from_file(path: &str) -> Result<Self, Diagnostic> {
+        let input = fs::read_to_string(path)
+            .map_err(|_| Diagnostic::error(None, format!("Unable to read the input from {path}")))?;
+
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("toml") => Self::from_toml(&input),
+            Some("json") => Self::from_json(&input),
+            _ => Self::from_csv(&input),
+        }
+    }
+
+    pub fn from_toml(input: &str) -> Result<Self, Diagnostic> {
+        let mut definition: Self = toml::from_str(input).map_err(|e: toml::de::Error| {
+            let line = e.span().map(|span| line_of_offset(input, span.start));
+            Diagnostic::error(line, format!("Invalid TOML galaxy file: {}", e.message()))
+        })?;
+        definition.set_lines(toml_table_lines(input, "planets"), toml_table_lines(input, "explorers"));
+        Ok(definition)
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, Diagnostic> {
+        let mut definition: Self = serde_json::from_str(input).map_err(|e| {
+            //serde_json counts the lines from 1, 0 means the error is not tied to a line
+            let line = Some(e.line()).filter(|line| *line > 0);
+            Diagnostic::error(line, format!("Invalid JSON galaxy file: {e}"))
+        })?;
+        definition.set_lines(json_array_lines(input, "planets"), json_array_lines(input, "explorers"));
+        Ok(definition)
+    }
+
+    //Lines are only assigned when every entry has been found, otherwise they would be misleading
+    fn set_lines(&mut self, planet_lines: Vec<usize>, explorer_lines: Vec<usize>) {
+        if planet_lines.len() == self.planets.len() {
+            for (planet, line) in self.planets.iter_mut().zip(planet_lines) {
+                planet.line = Some(line);
+            }
+        }
+        if explorer_lines.len() == self.explorers.len() {
+            for (explorer, line) in self.explorers.iter_mut().zip(explorer_lines) {
+                explorer.line = Some(line);
+            }
+        }
+    }
+
+    /// Parses the CSV format: every row is `planet_id, type_id, neighbors...`
+    ///
+    /// Numeric type ids are converted to planet type names, unknown ids get a random type.
+    /// The type can also be written by name, e.g. for the planets added with `register_planet`
+    pub fn from_csv(input: &str) -> Result<Self, Diagnostic> {
+        let mut planets = Vec::new();
+
+        for (line_num, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+            if columns.len() < 2 {
+                return Err(Diagnostic::error(Some(line_num + 1), "ID or Type missing".to_string()));
+            }
+
+            // u32 conversion, the type column can also be a planet name
+            let values: Vec<u32> = columns
+                .iter()
+                .enumerate()
+                .filter(|(col, _)| *col != 1)
+                .map(|(_, s)| {
+                    s.parse::<u32>().map_err(|_| {
+                        Diagnostic::error(Some(line_num + 1), format!("value '{s}' is not a u32"))
+                    })
+                })
+                .collect::<Result<Vec<u32>, Diagnostic>>()?;
+            let planet_type = match columns[1].parse::<u32>() {
+                Ok(type_id) => PlanetType::from_type_id(type_id).name().to_string(),
+                Err(_) => columns[1].to_string(),
+            };
+
+            planets.push(PlanetDefinition {
+                id: values[0],
+                planet_type,
+                neighbors: values[1..].to_vec(),
+                config: serde_json::Value::Null,
+                line: Some(line_num + 1),
+            });
+        }
+
+        Ok(Self {
+            planets,
+            ..Default::default()
+        })
+    }
+
+    /// Writes the galaxy in the CSV format, explorers, events and planet configs are not part of it
+    pub fn to_csv(&self) -> Result<String, String> {
+        let mut csv = String::new();
+        for planet in &self.planets {
+            let planet_type = PlanetType::from_name(&planet.planet_type)
+                .ok_or(format!("Planet {}: unknown planet type '{}'", planet.id, planet.planet_type))?;
+            //Custom planets have no numeric id and are written by name
+            let type_column = match planet_type.type_id() {
+                Some(type_id) => type_id.to_string(),
+                None => planet_type.name().to_string(),
+            };
+            let row: Vec<String> = [planet.id.to_string(), type_column]
+                .into_iter()
+                .chain(planet.neighbors.iter().map(|neighbor| neighbor.to_string()))
+                .collect();
+            csv.push_str(&row.join(", "));
+            csv.push('\n');
+        }
+        Ok(csv)
+    }
+
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|e| format!("Unable to write the galaxy as TOML: {e}"))
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Unable to write the galaxy as JSON: {e}"))
+    }
+}
+
+/// Line (starting from 1) of a byte offset of the input
+fn line_of_offset(input: &str, offset: usize) -> usize {
+    let before = &input.as_bytes()[..offset.min(input.len())];
+    before.iter().filter(|byte| **byte == b'\n').count() + 1
+}
+
+/// Lines (starting from 1) of the `[[name]]` headers of a TOML array of tables
+fn toml_table_lines(input: &str, name: &str) -> Vec<usize> {
+    let header = format!("[[{name}]]");
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == header)
+        .map(|(idx, _)| idx + 1)
+        .collect()
+}
+
+/// Lines (starting from 1) where the objects of the top level `"name": [...]` JSON array begin
+fn json_array_lines(input: &str, name: &str) -> Vec<usize> {
+    let key = format!("\"{name}\"");
+    let Some(key_pos) = input.find(&key) else {
+        return Vec::new();
+    };
+
+    let mut lines = Vec::new();
+    let mut line = input[..key_pos].matches('\n').count() + 1;
+    let mut depth: i32 = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in input[key_pos + key.len()..].chars() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' | '{' => {
+                //Depth 1 is the array itself, its objects open at depth 1
+                if c == '{' && depth == 1 {
+                    lines.push(line);
+                }
+                depth += 1;
+            }
+            ']' | '}' => {
+                depth -= 1;
+                if depth <= 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    lines
+}
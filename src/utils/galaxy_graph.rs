@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::utils::topology_analysis::PlanetGraph;
+
+/// Undirected graph of the galaxy, keyed by planet id
+///
+/// Every planet of the galaxy has an entry, even without links,
+/// so planets can be added and removed at any time without
+/// renumbering the others.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GalaxyGraph {
+    links: PlanetGraph,
+}
+
+impl GalaxyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the graph from the neighbors of every planet, links are made symmetric
+    ///
+    /// Fails if a planet is linked to itself or to a planet that is not in the list
+    pub fn from_neighbors(neighbors: &BTreeMap<u32, Vec<u32>>) -> Result<Self, String> {
+        let mut graph = Self::new();
+        for planet_id in neighbors.keys() {
+            graph.add_planet(*planet_id);
+        }
+        for (planet_id, planet_neighbors) in neighbors {
+            for neighbor in planet_neighbors {
+                graph.add_link(*planet_id, *neighbor)?;
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Returns false if the planet was already part of the galaxy
+    pub fn add_planet(&mut self, planet_id: u32) -> bool {
+        if self.links.contains_key(&planet_id) {
+            return false;
+        }
+        self.links.insert(planet_id, BTreeSet::new());
+        true
+    }
+
+    /// Removes the planet and all of its links, returns the planets it was linked to
+    pub fn remove_planet(&mut self, planet_id: u32) -> Option<BTreeSet<u32>> {
+        let neighbors = self.links.remove(&planet_id)?;
+        for neighbor in &neighbors {
+            if let Some(links) = self.links.get_mut(neighbor) {
+                links.remove(&planet_id);
+            }
+        }
+        Some(neighbors)
+    }
+
+    pub fn add_link(&mut self, planet_one: u32, planet_two: u32) -> Result<(), String> {
+        if planet_one == planet_two {
+            return Err(format!("Planet {planet_one} cannot be linked to itself"));
+        }
+        if !self.contains(planet_one) || !self.contains(planet_two) {
+            return Err(format!("Planet {planet_one} or planet {planet_two} is not part of the galaxy"));
+        }
+        self.links.entry(planet_one).or_default().insert(planet_two);
+        self.links.entry(planet_two).or_default().insert(planet_one);
+        Ok(())
+    }
+
+    /// Returns true if the link existed
+    pub fn remove_link(&mut self, planet_one: u32, planet_two: u32) -> bool {
+        let removed = self
+            .links
+            .get_mut(&planet_one)
+            .is_some_and(|links| links.remove(&planet_two));
+        if let Some(links) = self.links.get_mut(&planet_two) {
+            links.remove(&planet_one);
+        }
+        removed
+    }
+
+    pub fn contains(&self, planet_id: u32) -> bool {
+        self.links.contains_key(&planet_id)
+    }
+
+    pub fn are_linked(&self, planet_one: u32, planet_two: u32) -> bool {
+        self.links
+            .get(&planet_one)
+            .is_some_and(|links| links.contains(&planet_two))
+    }
+
+    /// Planets directly linked to `planet_id`, in ascending order
+    pub fn neighbors(&self, planet_id: u32) -> Vec<u32> {
+        self.links
+            .get(&planet_id)
+            .map(|links| links.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Every planet of the galaxy, in ascending order
+    pub fn planet_ids(&self) -> Vec<u32> {
+        self.links.keys().copied().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Every link once, as (lower id, higher id)
+    pub fn links(&self) -> Vec<(u32, u32)> {
+        self.links
+            .iter()
+            .flat_map(|(one, links)| links.iter().filter(|two| *two > one).map(move |two| (*one, *two)))
+            .collect()
+    }
+
+    /// The subgraph made of the planets accepted by `keep`
+    pub fn subgraph(&self, keep: impl Fn(u32) -> bool) -> PlanetGraph {
+        self.links
+            .iter()
+            .filter(|(id, _)| keep(**id))
+            .map(|(id, links)| (*id, links.iter().copied().filter(|other| keep(*other)).collect()))
+            .collect()
+    }
+
+    /// Dense version of the graph, `planet_ids[i]` is the planet of the i-th row and column
+    pub fn to_adjacency_matrix(&self) -> (Vec<u32>, Vec<Vec<bool>>) {
+        let planet_ids = self.planet_ids();
+        let adjacency = planet_ids
+            .iter()
+            .map(|one| planet_ids.iter().map(|two| self.are_linked(*one, *two)).collect())
+            .collect();
+        (planet_ids, adjacency)
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::galaxy_file::GalaxyDefinition;
+use crate::utils::registry::PlanetType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    /// The galaxy cannot be created
+    Error,
+    /// The galaxy can be created but it is probably not what was meant
+    Warning,
+}
+
+/// A problem found in a galaxy definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Line of the init file, when it is known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(line: Option<usize>, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            line,
+            message,
+        }
+    }
+
+    fn warning(line: Option<usize>, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            line,
+            message,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match self.line {
+            Some(line) => write!(f, "line {line}: {severity}: {}", self.message),
+            None => write!(f, "{severity}: {}", self.message),
+        }
+    }
+}
+
+impl GalaxyDefinition {
+    /// Checks the whole definition and returns every problem found, grouped by kind
+    ///
+    /// With `check_connectivity` a warning is added for every group of planets
+    /// that cannot be reached from the first one.
+    pub fn validate(&self, check_connectivity: bool) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        //Where every id is defined first: line and position in the planet list
+        let mut defined: FxHashMap<u32, (Option<usize>, usize)> = FxHashMap::default();
+        for (pos, planet) in self.planets.iter().enumerate() {
+            match defined.get(&planet.id) {
+                Some((first_line, first_pos)) => diagnostics.push(Diagnostic::error(
+                    planet.line,
+                    format!(
+                        "planet {} is defined more than once (first definition {})",
+                        planet.id,
+                        describe_location(*first_line, *first_pos)
+                    ),
+                )),
+                None => {
+                    defined.insert(planet.id, (planet.line, pos));
+                }
+            }
+
+            match PlanetType::from_name(&planet.planet_type) {
+                None => diagnostics.push(Diagnostic::error(
+                    planet.line,
+                    format!("planet {}: unknown planet type '{}'", planet.id, planet.planet_type),
+                )),
+                Some(planet_type) => {
+                    if let Err(e) = planet_type.check_config(&planet.config) {
+                        diagnostics.push(Diagnostic::error(planet.line, format!("planet {}: {e}", planet.id)));
+                    }
+                }
+            }
+        }
+
+        for planet in &self.planets {
+            let mut seen = FxHashSet::default();
+            for neighbor in &planet.neighbors {
+                if *neighbor == planet.id {
+                    diagnostics.push(Diagnostic::error(
+                        planet.line,
+                        format!("planet {} is linked to itself", planet.id),
+                    ));
+                } else if !defined.contains_key(neighbor) {
+                    diagnostics.push(Diagnostic::error(
+                        planet.line,
+                        format!("planet {} is linked to planet {neighbor}, which is not defined", planet.id),
+                    ));
+                } else if !seen.insert(*neighbor) {
+                    diagnostics.push(Diagnostic::warning(
+                        planet.line,
+                        format!("planet {} lists planet {neighbor} as neighbor more than once", planet.id),
+                    ));
+                }
+            }
+        }
+
+        let mut explorer_ids = FxHashSet::default();
+        for explorer in &self.explorers {
+            if !explorer_ids.insert(explorer.id) {
+                diagnostics.push(Diagnostic::error(
+                    explorer.line,
+                    format!("explorer {} is defined more than once", explorer.id),
+                ));
+            }
+            if !defined.contains_key(&explorer.planet_id) {
+                diagnostics.push(Diagnostic::error(
+                    explorer.line,
+                    format!(
+                        "explorer {} starts on planet {}, which is not defined",
+                        explorer.id, explorer.planet_id
+                    ),
+                ));
+            }
+        }
+
+        if let Some(events) = &self.events {
+            let invalid: BTreeSet<char> = events.chars().filter(|c| !matches!(c, 'S' | 'A')).collect();
+            if !invalid.is_empty() {
+                diagnostics.push(Diagnostic::error(
+                    None,
+                    format!("events can only contain 'S' (sunray) and 'A' (asteroid), found {invalid:?}"),
+                ));
+            }
+        }
+
+        if check_connectivity {
+            diagnostics.extend(self.disconnected_components());
+        }
+
+        diagnostics
+    }
+
+    //Every group of planets not reachable from the first planet, links are undirected
+    fn disconnected_components(&self) -> Vec<Diagnostic> {
+        let mut links: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+        for planet in &self.planets {
+            links.entry(planet.id).or_default();
+            for neighbor in &planet.neighbors {
+                links.entry(planet.id).or_default().push(*neighbor);
+                links.entry(*neighbor).or_default().push(planet.id);
+            }
+        }
+
+        let mut visited = FxHashSet::default();
+        let mut components: Vec<(Option<usize>, BTreeSet<u32>)> = Vec::new();
+        for planet in &self.planets {
+            if visited.contains(&planet.id) {
+                continue;
+            }
+            let mut component = BTreeSet::new();
+            let mut queue = VecDeque::from([planet.id]);
+            visited.insert(planet.id);
+            while let Some(id) = queue.pop_front() {
+                component.insert(id);
+                for next in links.get(&id).into_iter().flatten() {
+                    if visited.insert(*next) {
+                        queue.push_back(*next);
+                    }
+                }
+            }
+            components.push((planet.line, component));
+        }
+
+        let Some((_, others)) = components.split_first() else {
+            return Vec::new();
+        };
+        let first = self.planets[0].id;
+        others
+            .iter()
+            .map(|(line, component)| {
+                Diagnostic::warning(
+                    *line,
+                    format!("planets {component:?} cannot be reached from planet {first}"),
+                )
+            })
+            .collect()
+    }
+}
+
+fn describe_location(line: Option<usize>, pos: usize) -> String {
+    match line {
+        Some(line) => format!("at line {line}"),
+        None => format!("in entry {}", pos + 1),
+    }
+}
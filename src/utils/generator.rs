@@ -0,0 +1,293 @@
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+use crate::utils::galaxy_file::{GalaxyDefinition, GalaxyMetadata, PlanetDefinition};
+use crate::utils::registry::{PlanetClass, PlanetType};
+
+/// Shape of the links between the generated planets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphModel {
+    /// Every planet linked to the previous and the next one
+    Ring,
+    /// Planets placed row by row on a square grid, linked to the planets next to them
+    Grid,
+    /// Random spanning tree, plus every other link with the given probability
+    RandomConnected { extra_link_probability: f64 },
+    /// Watts-Strogatz: ring linked to `neighbors` planets on each side, every link is moved
+    /// to a random planet with the given probability
+    SmallWorld { neighbors: usize, rewire_probability: f64 },
+    /// Planet 0 linked to every other planet
+    Star,
+}
+
+impl GraphModel {
+    /// Parses the model names used by the command line tools, probabilities get default values:
+    /// "ring", "grid", "random", "small-world", "star"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "ring" => Some(GraphModel::Ring),
+            "grid" => Some(GraphModel::Grid),
+            "random" | "random-connected" => Some(GraphModel::RandomConnected {
+                extra_link_probability: 0.1,
+            }),
+            "small-world" => Some(GraphModel::SmallWorld {
+                neighbors: 2,
+                rewire_probability: 0.2,
+            }),
+            "star" => Some(GraphModel::Star),
+            _ => None,
+        }
+    }
+}
+
+/// A group of planets of the type mix
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeChoice {
+    /// Any builtin planet type
+    Any,
+    /// Any builtin planet type of the class
+    Class(PlanetClass),
+    Type(PlanetType),
+}
+
+/// How many planets of each kind are generated, e.g. "4C + 3D" or "1 Ciuc + 2D + 3 random"
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMix(pub Vec<(usize, TypeChoice)>);
+
+impl TypeMix {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut groups = Vec::new();
+        for group in input.split('+') {
+            let group = group.trim();
+            let digits = group.chars().take_while(|c| c.is_ascii_digit()).count();
+            let (count, kind) = group.split_at(digits);
+            let count: usize = count
+                .parse()
+                .map_err(|_| format!("Type mix '{group}': the count is missing"))?;
+
+            let kind = kind.trim();
+            let choice = match kind.to_ascii_uppercase().as_str() {
+                "A" => TypeChoice::Class(PlanetClass::A),
+                "B" => TypeChoice::Class(PlanetClass::B),
+                "C" => TypeChoice::Class(PlanetClass::C),
+                "D" => TypeChoice::Class(PlanetClass::D),
+                "RANDOM" | "ANY" | "*" => TypeChoice::Any,
+                _ => TypeChoice::Type(
+                    PlanetType::from_name(kind).ok_or(format!("Type mix '{group}': unknown planet type '{kind}'"))?,
+                ),
+            };
+            groups.push((count, choice));
+        }
+        Ok(Self(groups))
+    }
+
+    pub fn total(&self) -> usize {
+        self.0.iter().map(|(count, _)| count).sum()
+    }
+}
+
+/// Builds galaxies from parameters, the same parameters and seed always build the same galaxy
+///
+/// Random types and classes are chosen among `PlanetType::BUILTIN` only, so planets registered
+/// by other crates do not change the galaxy of a seed. They can still be asked for by name in the mix
+///
+/// ```ignore
+/// let csv = GalaxyGenerator::new(7, GraphModel::Ring, 42)
+///     .with_mix(TypeMix::parse("4C + 3D")?)
+///     .generate_csv()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GalaxyGenerator {
+    pub planet_count: usize,
+    pub model: GraphModel,
+    pub seed: u64,
+    /// Without a mix every planet gets a random type
+    pub mix: Option<TypeMix>,
+}
+
+impl GalaxyGenerator {
+    pub fn new(planet_count: usize, model: GraphModel, seed: u64) -> Self {
+        Self {
+            planet_count,
+            model,
+            seed,
+            mix: None,
+        }
+    }
+
+    pub fn with_mix(mut self, mix: TypeMix) -> Self {
+        self.mix = Some(mix);
+        self
+    }
+
+    /// Generates the galaxy, planet ids go from 0 to `planet_count - 1`
+    pub fn generate(&self) -> Result<GalaxyDefinition, String> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let types = self.planet_types(&mut rng)?;
+        let links = self.links(&mut rng)?;
+
+        let mut neighbors: Vec<Vec<u32>> = vec![Vec::new(); self.planet_count];
+        for (one, two) in links {
+            neighbors[one].push(two as u32);
+            neighbors[two].push(one as u32);
+        }
+
+        let planets = types
+            .into_iter()
+            .zip(neighbors)
+            .enumerate()
+            .map(|(id, (planet_type, mut neighbors))| {
+                neighbors.sort();
+                PlanetDefinition {
+                    id: id as u32,
+                    planet_type: planet_type.to_string(),
+                    neighbors,
+                    config: serde_json::Value::Null,
+                    line: None,
+                }
+            })
+            .collect();
+
+        Ok(GalaxyDefinition {
+            metadata: GalaxyMetadata {
+                name: Some(format!("{:?} of {} planets", self.model, self.planet_count)),
+                description: Some(format!("Generated with seed {}", self.seed)),
+                author: None,
+            },
+            planets,
+            ..Default::default()
+        })
+    }
+
+    /// Generates the galaxy in the CSV init file format
+    pub fn generate_csv(&self) -> Result<String, String> {
+        self.generate()?.to_csv()
+    }
+
+    fn planet_types(&self, rng: &mut StdRng) -> Result<Vec<PlanetType>, String> {
+        let all = PlanetType::BUILTIN.to_vec();
+
+        let Some(mix) = &self.mix else {
+            return Ok((0..self.planet_count).map(|_| *all.choose(rng).unwrap()).collect());
+        };
+        if mix.total() != self.planet_count {
+            return Err(format!(
+                "The type mix describes {} planets, but {} planets have been requested",
+                mix.total(),
+                self.planet_count
+            ));
+        }
+
+        let mut types = Vec::with_capacity(self.planet_count);
+        for (count, choice) in &mix.0 {
+            let candidates = match choice {
+                TypeChoice::Any => all.clone(),
+                TypeChoice::Class(class) => all.iter().filter(|typ| typ.class() == Some(*class)).copied().collect(),
+                TypeChoice::Type(typ) => vec![*typ],
+            };
+            if candidates.is_empty() {
+                return Err(format!("There are no planet types for {choice:?}"));
+            }
+            for _ in 0..*count {
+                types.push(*candidates.choose(rng).unwrap());
+            }
+        }
+        //The mix order must not decide where the types end up in the graph
+        types.shuffle(rng);
+        Ok(types)
+    }
+
+    //Every link once, as (lower index, higher index)
+    fn links(&self, rng: &mut StdRng) -> Result<BTreeSet<(usize, usize)>, String> {
+        let n = self.planet_count;
+        let mut links = BTreeSet::new();
+
+        match self.model {
+            GraphModel::Ring => {
+                for i in 0..n {
+                    link(&mut links, i, (i + 1) % n);
+                }
+            }
+            GraphModel::Grid => {
+                let columns = (n as f64).sqrt().ceil() as usize;
+                for i in 0..n {
+                    if (i + 1) % columns != 0 && i + 1 < n {
+                        link(&mut links, i, i + 1);
+                    }
+                    if i + columns < n {
+                        link(&mut links, i, i + columns);
+                    }
+                }
+            }
+            GraphModel::RandomConnected { extra_link_probability } => {
+                check_probability(extra_link_probability)?;
+                let mut order: Vec<usize> = (0..n).collect();
+                order.shuffle(rng);
+                for idx in 1..n {
+                    let parent = order[rng.random_range(0..idx)];
+                    link(&mut links, order[idx], parent);
+                }
+                for one in 0..n {
+                    for two in one + 1..n {
+                        if rng.random_bool(extra_link_probability) {
+                            link(&mut links, one, two);
+                        }
+                    }
+                }
+            }
+            GraphModel::SmallWorld { neighbors, rewire_probability } => {
+                check_probability(rewire_probability)?;
+                if n > 0 && 2 * neighbors >= n {
+                    return Err(format!(
+                        "A small world of {n} planets can have at most {} neighbors on each side",
+                        (n - 1) / 2
+                    ));
+                }
+                for i in 0..n {
+                    for j in 1..=neighbors {
+                        link(&mut links, i, (i + j) % n);
+                    }
+                }
+                for i in 0..n {
+                    for j in 1..=neighbors {
+                        let old = (i.min((i + j) % n), i.max((i + j) % n));
+                        if !rng.random_bool(rewire_probability) || !links.contains(&old) {
+                            continue;
+                        }
+                        let free: Vec<usize> = (0..n)
+                            .filter(|t| *t != i && !links.contains(&(i.min(*t), i.max(*t))))
+                            .collect();
+                        if let Some(target) = free.choose(rng) {
+                            links.remove(&old);
+                            link(&mut links, i, *target);
+                        }
+                    }
+                }
+            }
+            GraphModel::Star => {
+                for i in 1..n {
+                    link(&mut links, 0, i);
+                }
+            }
+        }
+        Ok(links)
+    }
+}
+
+fn link(links: &mut BTreeSet<(usize, usize)>, one: usize, two: usize) {
+    if one != two {
+        links.insert((one.min(two), one.max(two)));
+    }
+}
+
+fn check_probability(probability: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&probability) {
+        Ok(())
+    } else {
+        Err(format!("Probability {probability} is not between 0 and 1"))
+    }
+}
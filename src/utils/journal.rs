@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::explorer::BagType;
+use crate::messages::UiToGame;
+use crate::messages::json::resource_types;
+use crate::utils::galaxy_graph::GalaxyGraph;
+use crate::utils::registry::PlanetType;
+use crate::utils::topology_analysis::TopologyAnalysis;
+use crate::utils::{ExplorerSnapshot, GalaxySnapshot, PlanetSnapshot, Status, TopologySnapshot};
+
+/// Something the orchestrator saw or did, see `JournalEntry`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalEvent {
+    /// Planets, types and links of the galaxy once it is created
+    GalaxyLoaded {
+        planets: BTreeMap<u32, PlanetType>,
+        links: Vec<(u32, u32)>,
+    },
+    SunraySent { planet_id: u32 },
+    AsteroidSent { planet_id: u32 },
+    SunrayAck { planet_id: u32 },
+    AsteroidAck { planet_id: u32, rocket: bool },
+    PlanetStatus { planet_id: u32, status: Status },
+    ExplorerStatus { explorer_id: u32, status: Status },
+    ExplorerSpawned { explorer_id: u32, planet_id: u32 },
+    ExplorerMoved { explorer_id: u32, planet_id: u32 },
+    PlanetAdded {
+        planet_id: u32,
+        planet_type: PlanetType,
+        neighbors: Vec<u32>,
+    },
+    PlanetRemoved { planet_id: u32 },
+    LinkDestroyed { planet_one: u32, planet_two: u32 },
+    /// Bag received from an explorer, whoever asked for it
+    ExplorerBag {
+        explorer_id: u32,
+        #[serde(with = "resource_types")]
+        bag: BagType,
+    },
+    /// Neighbors asked by an explorer, with the ones it was answered
+    ExplorerNeighbors { explorer_id: u32, neighbors: Vec<u32> },
+    /// Every command received from the UI, in the order it was handled
+    UiCommand(UiToGame),
+    /// The orchestrator was reset, the following entries describe a new game
+    Reset,
+}
+
+/// One line of the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub tick: u64,
+    pub event: JournalEvent,
+}
+
+/// Append-only journal of a game, one JSON line per entry
+pub(crate) struct Journal {
+    file: File,
+}
+
+impl Journal {
+    /// Opens the journal at `path`, a journal that already has a game goes on after a `Reset`
+    pub(crate) fn create(path: &Path) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Unable to open the journal {}: {e}", path.display()))?;
+        let previous_game = file
+            .metadata()
+            .map_err(|e| format!("Unable to open the journal {}: {e}", path.display()))?
+            .len()
+            > 0;
+        let mut journal = Self { file };
+        //Ticks start again from zero, the replay must not mix the two games
+        if previous_game {
+            journal.record(0, JournalEvent::Reset)?;
+        }
+        Ok(journal)
+    }
+
+    //Every entry is written right away, the journal of a crashed game is still complete
+    pub(crate) fn record(&mut self, tick: u64, event: JournalEvent) -> Result<(), String> {
+        let line = serde_json::to_string(&JournalEntry { tick, event })
+            .map_err(|e| format!("Unable to encode the journal entry: {e}"))?;
+        writeln!(self.file, "{line}").map_err(|e| format!("Unable to write the journal: {e}"))
+    }
+}
+
+/// A finished game read back from its journal
+///
+/// The galaxy is rebuilt from the entries only, no planet or explorer runs,
+/// so a UI can move back and forth between the ticks with `snapshot_at`
+#[derive(Debug, Clone)]
+pub struct GalaxyReplay {
+    entries: Vec<JournalEntry>,
+}
+
+impl GalaxyReplay {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read the journal {}: {e}", path.display()))?;
+        Self::from_jsonl(&content)
+    }
+
+    pub fn from_jsonl(content: &str) -> Result<Self, String> {
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_num, line)| {
+                serde_json::from_str(line).map_err(|e| format!("line {}: invalid journal entry: {e}", line_num + 1))
+            })
+            .collect::<Result<Vec<JournalEntry>, String>>()?;
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    pub fn last_tick(&self) -> u64 {
+        self.last_game().iter().map(|entry| entry.tick).max().unwrap_or(0)
+    }
+
+    //Ticks restart from zero after a reset, only the last game is replayed
+    fn last_game(&self) -> &[JournalEntry] {
+        let last_reset = self
+            .entries
+            .iter()
+            .rposition(|entry| matches!(entry.event, JournalEvent::Reset))
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        &self.entries[last_reset..]
+    }
+
+    /// State of the galaxy at the end of `tick`
+    ///
+    /// `version` is the number of entries applied, so the UI sees every step as a new frame
+    pub fn snapshot_at(&self, tick: u64) -> GalaxySnapshot {
+        let mut state = ReplayState::default();
+        let mut applied = 0;
+        for entry in self.last_game() {
+            if entry.tick > tick {
+                break;
+            }
+            state.apply(&entry.event);
+            applied += 1;
+        }
+        state.snapshot(tick, applied)
+    }
+}
+
+#[derive(Default)]
+struct ReplayState {
+    topology: GalaxyGraph,
+    planets: BTreeMap<u32, PlanetSnapshot>,
+    explorers: BTreeMap<u32, ExplorerSnapshot>,
+    destroyed_links: BTreeSet<(u32, u32)>,
+}
+
+impl ReplayState {
+    fn apply(&mut self, event: &JournalEvent) {
+        match event {
+            JournalEvent::GalaxyLoaded { planets, links } => {
+                for (planet_id, planet_type) in planets {
+                    self.topology.add_planet(*planet_id);
+                    self.planet(*planet_id).planet_type = Some(*planet_type);
+                }
+                for (planet_one, planet_two) in links {
+                    let _ = self.topology.add_link(*planet_one, *planet_two);
+                }
+            }
+            JournalEvent::PlanetStatus { planet_id, status } => {
+                self.planet(*planet_id).status = status.clone();
+            }
+            JournalEvent::ExplorerStatus { explorer_id, status } => {
+                self.explorer(*explorer_id).status = status.clone();
+            }
+            JournalEvent::ExplorerSpawned { explorer_id, planet_id }
+            | JournalEvent::ExplorerMoved { explorer_id, planet_id } => {
+                self.explorer(*explorer_id).planet_id = *planet_id;
+            }
+            JournalEvent::PlanetAdded {
+                planet_id,
+                planet_type,
+                neighbors,
+            } => {
+                self.topology.add_planet(*planet_id);
+                self.planet(*planet_id).planet_type = Some(*planet_type);
+                for neighbor in neighbors {
+                    self.topology.add_planet(*neighbor);
+                    let _ = self.topology.add_link(*planet_id, *neighbor);
+                }
+            }
+            JournalEvent::PlanetRemoved { planet_id } => {
+                self.topology.remove_planet(*planet_id);
+                self.planets.remove(planet_id);
+                self.destroyed_links
+                    .retain(|(one, two)| one != planet_id && two != planet_id);
+            }
+            JournalEvent::LinkDestroyed { planet_one, planet_two } => {
+                if self.topology.remove_link(*planet_one, *planet_two) {
+                    self.destroyed_links
+                        .insert((*planet_one.min(planet_two), *planet_one.max(planet_two)));
+                }
+            }
+            JournalEvent::Reset => *self = ReplayState::default(),
+            //Messages and commands explain the changes, the changes themselves are separate entries
+            JournalEvent::SunraySent { .. }
+            | JournalEvent::AsteroidSent { .. }
+            | JournalEvent::SunrayAck { .. }
+            | JournalEvent::AsteroidAck { .. }
+            | JournalEvent::ExplorerBag { .. }
+            | JournalEvent::ExplorerNeighbors { .. }
+            | JournalEvent::UiCommand(_) => {}
+        }
+    }
+
+    //Planets appear with their first status, the type comes with the galaxy
+    fn planet(&mut self, planet_id: u32) -> &mut PlanetSnapshot {
+        self.planets.entry(planet_id).or_insert(PlanetSnapshot {
+            status: Status::Paused,
+            planet_type: None,
+        })
+    }
+
+    fn explorer(&mut self, explorer_id: u32) -> &mut ExplorerSnapshot {
+        self.explorers.entry(explorer_id).or_insert(ExplorerSnapshot {
+            status: Status::Paused,
+            planet_id: 0,
+        })
+    }
+
+    fn snapshot(&self, tick: u64, version: u64) -> GalaxySnapshot {
+        let (planet_ids, adjacency) = self.topology.to_adjacency_matrix();
+        let live_graph = self
+            .topology
+            .subgraph(|id| !self.planets.get(&id).is_some_and(|planet| planet.status.is_dead()));
+        GalaxySnapshot {
+            version,
+            tick,
+            topology: TopologySnapshot {
+                planet_ids,
+                adjacency,
+                destroyed_links: self.destroyed_links.iter().copied().collect(),
+            },
+            analysis: TopologyAnalysis::compute(live_graph),
+            planets: self.planets.clone(),
+            explorers: self.explorers.clone(),
+        }
+    }
+}
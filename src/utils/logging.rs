@@ -0,0 +1,167 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Once;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use common_game::logging::{ActorType, Channel, EventType, LogEvent, Participant, Payload};
+
+use crate::settings;
+use crate::utils::errors::Actor;
+
+/// Where the log events are written, see `settings::set_log_sink`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogSink {
+    Stderr,
+    /// Events are appended to the file, it is created if missing
+    File(PathBuf),
+    Off,
+}
+
+//Open destination of the events, kept by the settings
+pub(crate) enum LogWriter {
+    Stderr,
+    File(File),
+    Off,
+}
+
+impl LogWriter {
+    pub(crate) fn open(sink: LogSink) -> Result<Self, String> {
+        match sink {
+            LogSink::Stderr => Ok(LogWriter::Stderr),
+            LogSink::File(path) => OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map(LogWriter::File)
+                .map_err(|e| format!("Unable to open the log file {}: {e}", path.display())),
+            LogSink::Off => Ok(LogWriter::Off),
+        }
+    }
+
+    fn write_line(&mut self, line: &str) {
+        //A log that cannot be written must not stop the game
+        let _ = match self {
+            LogWriter::Stderr => writeln!(std::io::stderr(), "{line}"),
+            LogWriter::File(file) => writeln!(file, "{line}"),
+            LogWriter::Off => Ok(()),
+        };
+    }
+}
+
+/// Verbosity of a channel, the lower the more important
+pub(crate) fn channel_rank(channel: &Channel) -> u8 {
+    match channel {
+        Channel::Error => 0,
+        Channel::Warning => 1,
+        Channel::Info => 2,
+        Channel::Debug => 3,
+        Channel::Trace => 4,
+    }
+}
+
+pub(crate) fn channel_of_rank(rank: u8) -> Channel {
+    match rank {
+        0 => Channel::Error,
+        1 => Channel::Warning,
+        2 => Channel::Info,
+        3 => Channel::Debug,
+        _ => Channel::Trace,
+    }
+}
+
+//Tick of the game, published by the orchestrator for the threads that do not know it (the explorers)
+static GAME_TICK: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn publish_tick(tick: u64) {
+    GAME_TICK.store(tick, Ordering::Relaxed);
+}
+
+/// Last tick published by the orchestrator, used by the explorers to log their events
+pub(crate) fn game_tick() -> u64 {
+    GAME_TICK.load(Ordering::Relaxed)
+}
+
+//Writes the records of the `log` crate, where the common_game events are emitted, to the sink of the settings
+struct SinkLogger;
+
+impl log::Log for SinkLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        settings::log_enabled(&channel_of_level(metadata.level()))
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            let line = record.args().to_string();
+            settings::with_log_writer(|writer| writer.write_line(&line));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static SINK_LOGGER: SinkLogger = SinkLogger;
+static INSTALL_LOGGER: Once = Once::new();
+
+//An application that already installed its own logger keeps it, the events reach it instead of the sink
+fn install_logger() {
+    INSTALL_LOGGER.call_once(|| {
+        if log::set_logger(&SINK_LOGGER).is_ok() {
+            log::set_max_level(log::LevelFilter::Trace);
+        }
+    });
+}
+
+fn channel_of_level(level: log::Level) -> Channel {
+    match level {
+        log::Level::Error => Channel::Error,
+        log::Level::Warn => Channel::Warning,
+        log::Level::Info => Channel::Info,
+        log::Level::Debug => Channel::Debug,
+        log::Level::Trace => Channel::Trace,
+    }
+}
+
+fn participant(actor: Actor) -> Option<Participant> {
+    match actor {
+        Actor::Planet(planet_id) => Some(Participant::new(ActorType::Planet, planet_id)),
+        Actor::Explorer(explorer_id) => Some(Participant::new(ActorType::Explorer, explorer_id)),
+        Actor::AllPlanets | Actor::AllExplorers | Actor::Ui => None,
+    }
+}
+
+/// Emits an event through `common_game::logging` if its channel is enabled, use `log_event!` to skip formatting disabled events
+///
+/// The event goes from the actor (the orchestrator for the game itself) to the orchestrator,
+/// the tick, the name of the event and the message are in its payload
+pub(crate) fn write_event(channel: Channel, tick: u64, actor: Option<Actor>, event: &str, message: String) {
+    if !settings::log_enabled(&channel) {
+        return;
+    }
+    install_logger();
+    let orchestrator = Participant::new(ActorType::Orchestrator, 0u32);
+    let event_type = match actor {
+        Some(Actor::Planet(_) | Actor::AllPlanets) => EventType::MessagePlanetToOrchestrator,
+        Some(Actor::Explorer(_) | Actor::AllExplorers) => EventType::MessageExplorerToOrchestrator,
+        Some(Actor::Ui) | None => EventType::InternalOrchestratorAction,
+    };
+    let mut payload = Payload::new();
+    payload.insert("tick".to_string(), tick.to_string());
+    payload.insert("event".to_string(), event.to_string());
+    if let Some(actor) = actor {
+        payload.insert("actor".to_string(), actor.to_string());
+    }
+    payload.insert("message".to_string(), message);
+    let sender = actor.and_then(participant).unwrap_or_else(|| orchestrator.clone());
+    LogEvent::new(Some(sender), Some(orchestrator), event_type, channel, payload).emit();
+}
+
+/// `log_event!(channel, tick, actor, "Event", "format {}", args)`, the message is formatted only when the channel is enabled
+macro_rules! log_event {
+    ($channel:expr, $tick:expr, $actor:expr, $event:expr, $($arg:tt)*) => {
+        if $crate::settings::log_enabled(&$channel) {
+            $crate::utils::logging::write_event($channel, $tick, $actor, $event, format!($($arg)*));
+        }
+    };
+}
+pub(crate) use log_event;
@@ -0,0 +1,204 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Formats in which the metrics can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetricsFormat {
+    /// One row per tick, with a latency column for every planet
+    Csv,
+    /// Every tick and the latency of every planet over the whole game
+    Json,
+}
+
+impl MetricsFormat {
+    /// Csv for `.csv` files, Json otherwise
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => MetricsFormat::Csv,
+            _ => MetricsFormat::Json,
+        }
+    }
+}
+
+/// What happened during a single tick
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TickMetrics {
+    pub tick: u64,
+    /// Planets not dead at the end of the tick
+    pub live_planets: usize,
+    pub sunrays_sent: u32,
+    pub asteroids_sent: u32,
+    /// Asteroids destroyed by a rocket of the planet
+    pub rockets_fired: u32,
+    /// Planets that died or crashed
+    pub planets_destroyed: u32,
+    /// Messages received from the explorers, without the answers to the lifecycle commands and to the bag polls
+    pub explorer_actions: u32,
+    /// Mean time between a request and its ack, for every planet that answered in the tick
+    pub latency_ms: BTreeMap<u32, f64>,
+}
+
+/// Time a planet takes to acknowledge the requests of the orchestrator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub acks: u64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencyStats {
+    fn add(&mut self, latency_ms: f64) {
+        self.mean_ms = (self.mean_ms * self.acks as f64 + latency_ms) / (self.acks + 1) as f64;
+        self.max_ms = self.max_ms.max(latency_ms);
+        self.acks += 1;
+    }
+}
+
+/// Metrics of a whole game, collected by the orchestrator tick by tick
+///
+/// Used to compare the planet crates, export them with `export` at the end of the game
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameMetrics {
+    /// Every tick already ended, followed by the current one
+    pub ticks: Vec<TickMetrics>,
+    pub planet_latency: BTreeMap<u32, LatencyStats>,
+    //Send time of the requests not acknowledged yet, in the same order as the acks
+    #[serde(skip)]
+    sent: BTreeMap<u32, VecDeque<Instant>>,
+    //Latencies of the current tick, averaged when it ends
+    #[serde(skip)]
+    tick_latencies: BTreeMap<u32, Vec<f64>>,
+}
+
+impl GameMetrics {
+    pub fn new() -> Self {
+        Self::starting_at(0)
+    }
+
+    /// Metrics of a game that goes on from `tick`, e.g. one restored from a checkpoint
+    pub fn starting_at(tick: u64) -> Self {
+        Self {
+            ticks: vec![TickMetrics {
+                tick,
+                ..TickMetrics::default()
+            }],
+            ..Self::default()
+        }
+    }
+
+    fn current(&mut self) -> &mut TickMetrics {
+        if self.ticks.is_empty() {
+            self.ticks.push(TickMetrics::default());
+        }
+        self.ticks.last_mut().unwrap()
+    }
+
+    /// Ends the current tick and starts the next one
+    pub(crate) fn end_tick(&mut self, live_planets: usize, next_tick: u64) {
+        let latencies = std::mem::take(&mut self.tick_latencies);
+        let current = self.current();
+        current.live_planets = live_planets;
+        current.latency_ms = latencies
+            .into_iter()
+            .map(|(planet_id, samples)| (planet_id, samples.iter().sum::<f64>() / samples.len() as f64))
+            .collect();
+        self.ticks.push(TickMetrics {
+            tick: next_tick,
+            live_planets,
+            ..TickMetrics::default()
+        });
+    }
+
+    pub(crate) fn sunray_sent(&mut self) {
+        self.current().sunrays_sent += 1;
+    }
+
+    pub(crate) fn asteroid_sent(&mut self) {
+        self.current().asteroids_sent += 1;
+    }
+
+    pub(crate) fn rocket_fired(&mut self) {
+        self.current().rockets_fired += 1;
+    }
+
+    pub(crate) fn planet_destroyed(&mut self, planet_id: u32) {
+        self.current().planets_destroyed += 1;
+        self.sent.remove(&planet_id);
+    }
+
+    pub(crate) fn explorer_action(&mut self) {
+        self.current().explorer_actions += 1;
+    }
+
+    pub(crate) fn request_sent(&mut self, planet_id: u32, now: Instant) {
+        self.sent.entry(planet_id).or_default().push_back(now);
+    }
+
+    pub(crate) fn ack_received(&mut self, planet_id: u32, now: Instant) {
+        let Some(sent) = self.sent.get_mut(&planet_id).and_then(|sent| sent.pop_front()) else {
+            return;
+        };
+        let latency_ms = now.duration_since(sent).as_micros() as f64 / 1000.0;
+        self.planet_latency.entry(planet_id).or_default().add(latency_ms);
+        self.tick_latencies.entry(planet_id).or_default().push(latency_ms);
+    }
+
+    pub fn export(&self, format: MetricsFormat) -> Result<String, String> {
+        match format {
+            MetricsFormat::Csv => Ok(self.to_csv()),
+            MetricsFormat::Json => {
+                serde_json::to_string_pretty(self).map_err(|e| format!("Unable to encode the metrics: {e}"))
+            }
+        }
+    }
+
+    /// One row per tick, the latency of a planet is empty in the ticks it did not answer
+    pub fn to_csv(&self) -> String {
+        let planet_ids: BTreeSet<u32> = self
+            .ticks
+            .iter()
+            .flat_map(|tick| tick.latency_ms.keys().copied())
+            .collect();
+
+        let mut csv =
+            String::from("tick,live_planets,sunrays_sent,asteroids_sent,rockets_fired,planets_destroyed,explorer_actions");
+        for planet_id in &planet_ids {
+            let _ = write!(csv, ",latency_ms_planet_{planet_id}");
+        }
+        csv.push('\n');
+
+        for tick in &self.ticks {
+            let _ = write!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                tick.tick,
+                tick.live_planets,
+                tick.sunrays_sent,
+                tick.asteroids_sent,
+                tick.rockets_fired,
+                tick.planets_destroyed,
+                tick.explorer_actions
+            );
+            for planet_id in &planet_ids {
+                match tick.latency_ms.get(planet_id) {
+                    Some(latency) => {
+                        let _ = write!(csv, ",{latency:.3}");
+                    }
+                    None => csv.push(','),
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Writes the metrics to a file, in CSV if its extension is `.csv` and in JSON otherwise
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let content = self.export(MetricsFormat::from_path(path))?;
+        std::fs::write(path, content).map_err(|e| format!("Unable to write the metrics {}: {e}", path.display()))
+    }
+}
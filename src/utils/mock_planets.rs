@@ -0,0 +1,137 @@
+use std::thread;
+use std::time::Duration;
+
+use common_game::components::planet::{DummyPlanetState, Planet, PlanetAI, PlanetState, PlanetType as PlanetClassType};
+use common_game::components::resource::{BasicResourceType, Combinator, Generator};
+use common_game::components::rocket::Rocket;
+use common_game::components::sunray::Sunray;
+use common_game::protocols::planet_explorer::{ExplorerToPlanet, PlanetToExplorer};
+use serde::Deserialize;
+
+use crate::utils::registry::{PlanetCapabilities, PlanetClass, PlanetEntry};
+use crate::utils::types::{PlanetConfig, PlanetFactory};
+
+/// Scripted behaviour of a mock planet, read from its config
+///
+/// e.g. `config = { always_rocket = true, dies_after = 3, ack_delay_ms = 100 }`.
+/// The default mock never has a rocket, so it dies on the first asteroid
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MockBehaviour {
+    /// Builds a rocket with every sunray, so it survives every asteroid that follows a sunray
+    pub always_rocket: bool,
+    /// The n-th asteroid destroys the planet, even if it has a rocket
+    pub dies_after: Option<u32>,
+    /// Every sunray and asteroid is acknowledged after this delay
+    pub ack_delay_ms: u64,
+    /// The planet hangs on the first sunray or asteroid and never acknowledges it
+    pub never_ack: bool,
+    /// The AI panics on the first sunray, like a broken planet of another crate
+    pub panic_on_sunray: bool,
+}
+
+impl MockBehaviour {
+    pub fn from_config(config: &PlanetConfig) -> Result<Self, String> {
+        if config.is_null() {
+            return Ok(Self::default());
+        }
+        serde_json::from_value(config.clone()).map_err(|e| format!("invalid mock planet config: {e}"))
+    }
+}
+
+struct MockAi {
+    behaviour: MockBehaviour,
+    asteroids: u32,
+}
+
+impl MockAi {
+    //Called before every ack
+    fn wait(&self) {
+        if self.behaviour.never_ack {
+            loop {
+                thread::park();
+            }
+        }
+        thread::sleep(Duration::from_millis(self.behaviour.ack_delay_ms));
+    }
+}
+
+impl PlanetAI for MockAi {
+    fn handle_sunray(&mut self, state: &mut PlanetState, _generator: &Generator, _combinator: &Combinator, sunray: Sunray) {
+        if self.behaviour.panic_on_sunray {
+            panic!("mock planet hit by a sunray");
+        }
+        self.wait();
+        if self.behaviour.always_rocket {
+            state.cell_mut(0).charge(sunray);
+            let _ = state.build_rocket(0);
+        }
+    }
+
+    fn handle_asteroid(&mut self, state: &mut PlanetState, _generator: &Generator, _combinator: &Combinator) -> Option<Rocket> {
+        self.wait();
+        self.asteroids += 1;
+        if self.behaviour.dies_after.is_some_and(|limit| self.asteroids >= limit) {
+            return None;
+        }
+        state.take_rocket()
+    }
+
+    fn handle_internal_state_req(
+        &mut self,
+        state: &mut PlanetState,
+        _generator: &Generator,
+        _combinator: &Combinator,
+    ) -> DummyPlanetState {
+        state.to_dummy()
+    }
+
+    //Mocks support no resources, explorers get empty answers
+    fn handle_explorer_msg(
+        &mut self,
+        _state: &mut PlanetState,
+        _generator: &Generator,
+        _combinator: &Combinator,
+        msg: ExplorerToPlanet,
+    ) -> Option<PlanetToExplorer> {
+        match msg {
+            ExplorerToPlanet::SupportedResourceRequest { .. } => Some(PlanetToExplorer::SupportedResourceResponse {
+                resource_list: Default::default(),
+            }),
+            ExplorerToPlanet::SupportedCombinationRequest { .. } => {
+                Some(PlanetToExplorer::SupportedCombinationResponse {
+                    combination_list: Default::default(),
+                })
+            }
+            ExplorerToPlanet::GenerateResourceRequest { .. } => {
+                Some(PlanetToExplorer::GenerateResourceResponse { resource: None })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Registry entry of `PlanetType::MOCK`, a class A planet driven by `MockBehaviour`
+pub(crate) fn mock_entry() -> PlanetEntry {
+    PlanetEntry {
+        capabilities: PlanetCapabilities::of_class(PlanetClass::A)
+            .with_basic_resources(vec![])
+            .with_complex_resources(vec![]),
+        factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+            let ai = MockAi {
+                behaviour: MockBehaviour::from_config(config)?,
+                asteroids: 0,
+            };
+            //The generation rule is required by the class, the AI never uses it
+            Planet::new(
+                planet_id,
+                PlanetClassType::A,
+                Box::new(ai),
+                vec![BasicResourceType::Carbon],
+                vec![],
+                (rx_o, tx_o),
+                rx_e,
+            )
+        }) as PlanetFactory,
+    }
+}
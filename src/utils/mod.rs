@@ -1,6 +1,22 @@
 pub mod registry;
 pub mod types;
 pub mod state_enums;
+pub mod snapshot;
+pub mod galaxy_file;
+pub mod galaxy_validation;
+pub mod export;
+pub mod generator;
+pub mod topology_analysis;
+pub mod galaxy_graph;
+pub mod errors;
+pub mod logging;
+pub mod journal;
+pub mod checkpoint;
+pub mod scoring;
+pub mod metrics;
+#[cfg(any(test, feature = "mock-planets"))]
+pub mod mock_planets;
 
 pub use types::*;
-pub use state_enums::*;
\ No newline at end of file
+pub use state_enums::*;
+pub use snapshot::*;
@@ -1,18 +1,23 @@
-use common_game::components::resource::BasicResourceType;
+use common_game::components::resource::{BasicResourceType, ComplexResourceType};
 use once_cell::sync::Lazy;
 
-use super::types::PlanetFactory;
+use super::types::{PlanetConfig, PlanetFactory};
 use crate::utils::registry::PlanetType::{
     BlackAdidasShoe, Ciuc, HoustonWeHaveABorrow, ImmutableCosmicBorrow, OneMillionCrabs, RustyCrab, Rustrelli,
 };
+use common_game::components::planet::Planet;
+use common_game::protocols::orchestrator_planet::{OrchestratorToPlanet, PlanetToOrchestrator};
+use common_game::protocols::planet_explorer::ExplorerToPlanet;
+use crossbeam_channel::{Receiver, Sender};
 use rand::seq::IndexedRandom;
-use std::{collections::HashMap};
-// Importiamo il trait per poter usare .iter()
-use strum::IntoEnumIterator;
-// Importiamo la macro per il derive
-use strum_macros::EnumIter;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-#[derive(Debug, EnumIter, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub enum PlanetType {
     BlackAdidasShoe,
     Ciuc,
@@ -21,86 +26,653 @@ pub enum PlanetType {
     OneMillionCrabs,
     Rustrelli,
     RustyCrab,
+    /// Planet of another crate, added with `register_planet`
+    Custom(&'static str),
 }
+/// Planet classes of the common game specification
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum PlanetClass {
+    A,
+    B,
+    C,
+    D,
+}
+
+/// Maximum number of something a planet can have, see `ClassRules`
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Limit {
+    AtMost(u32),
+    Unbounded,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum EnergyCells {
+    One,
+    Many,
+}
+
+/// Rules shared by every planet of a class (see Decisione_pianeta.md)
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct ClassRules {
+    pub energy_cells: EnergyCells,
+    pub generation_rules: Limit,
+    pub rockets: Limit,
+    pub combination_rules: Limit,
+}
+
+impl PlanetClass {
+    pub fn rules(&self) -> ClassRules {
+        let (energy_cells, generation_rules, rockets, combination_rules) = match self {
+            PlanetClass::A => (EnergyCells::Many, Limit::AtMost(1), Limit::AtMost(1), Limit::AtMost(0)),
+            PlanetClass::B => (EnergyCells::One, Limit::Unbounded, Limit::AtMost(0), Limit::AtMost(1)),
+            PlanetClass::C => (EnergyCells::One, Limit::AtMost(1), Limit::AtMost(1), Limit::Unbounded),
+            PlanetClass::D => (EnergyCells::Many, Limit::Unbounded, Limit::AtMost(0), Limit::AtMost(0)),
+        };
+        ClassRules {
+            energy_cells,
+            generation_rules,
+            rockets,
+            combination_rules,
+        }
+    }
+}
+
+/// What a planet type can do, kept in its registry entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetCapabilities {
+    pub class: PlanetClass,
+    pub rules: ClassRules,
+    /// Number of energy cells, `None` if the class has many and the planet crate does not say how many
+    pub energy_cell_count: Option<u32>,
+    /// Basic resources the planet can generate, `None` if the planet crate does not declare them
+    pub basic_resources: Option<Vec<BasicResourceType>>,
+    /// Complex resources the planet can combine, `None` if the planet crate does not declare them
+    pub complex_resources: Option<Vec<ComplexResourceType>>,
+}
+
+impl PlanetCapabilities {
+    /// Capabilities known from the class only
+    pub fn of_class(class: PlanetClass) -> Self {
+        let rules = class.rules();
+        Self {
+            class,
+            rules,
+            energy_cell_count: match rules.energy_cells {
+                EnergyCells::One => Some(1),
+                EnergyCells::Many => None,
+            },
+            basic_resources: None,
+            complex_resources: None,
+        }
+    }
+
+    pub fn with_energy_cell_count(mut self, count: u32) -> Self {
+        self.energy_cell_count = Some(count);
+        self
+    }
+
+    pub fn with_basic_resources(mut self, resources: Vec<BasicResourceType>) -> Self {
+        self.basic_resources = Some(resources);
+        self
+    }
+
+    pub fn with_complex_resources(mut self, resources: Vec<ComplexResourceType>) -> Self {
+        self.complex_resources = Some(resources);
+        self
+    }
+
+    pub fn has_many_energy_cells(&self) -> bool {
+        self.rules.energy_cells == EnergyCells::Many
+    }
+
+    pub fn can_build_rockets(&self) -> bool {
+        self.rules.rockets != Limit::AtMost(0)
+    }
+
+    pub fn can_combine(&self) -> bool {
+        self.rules.combination_rules != Limit::AtMost(0)
+    }
+
+    /// False only if the planet declares its basic resources and `resource` is not one of them
+    pub fn may_generate(&self, resource: BasicResourceType) -> bool {
+        self.basic_resources
+            .as_ref()
+            .is_none_or(|resources| resources.contains(&resource))
+    }
+}
+
+/// Entry of `PLANET_REGISTRY`: how to build a planet type and what it can do
+pub struct PlanetEntry {
+    pub capabilities: PlanetCapabilities,
+    pub factory: PlanetFactory,
+}
+
 impl PlanetType {
+    /// The planets of this crate, in the order of their CSV type ids
+    pub const BUILTIN: [PlanetType; 7] = [
+        BlackAdidasShoe,
+        Ciuc,
+        HoustonWeHaveABorrow,
+        ImmutableCosmicBorrow,
+        OneMillionCrabs,
+        Rustrelli,
+        RustyCrab,
+    ];
+
+    /// Scriptable planet for the tests, see `MockBehaviour`
+    #[cfg(any(test, feature = "mock-planets"))]
+    pub const MOCK: PlanetType = PlanetType::Custom("MockPlanet");
+
+    /// Every registered planet type, the builtin ones first and then the custom ones by name
+    ///
+    /// The mock planet is left out, so it is never chosen at random or for a class
+    pub fn all() -> Vec<Self> {
+        Self::registered()
+            .into_iter()
+            .filter(|typ| !typ.is_mock())
+            .collect()
+    }
+
+    //Every type with an entry, the mock planet included
+    fn registered() -> Vec<Self> {
+        let mut custom: Vec<PlanetType> = PLANET_REGISTRY
+            .read()
+            .unwrap()
+            .keys()
+            .filter(|typ| matches!(typ, PlanetType::Custom(_)))
+            .copied()
+            .collect();
+        custom.sort_by_key(|typ| typ.name());
+        Self::BUILTIN.into_iter().chain(custom).collect()
+    }
+
+    fn is_mock(&self) -> bool {
+        #[cfg(any(test, feature = "mock-planets"))]
+        if *self == PlanetType::MOCK {
+            return true;
+        }
+        false
+    }
+
     pub fn random() -> Self {
         let mut rng = rand::rng();
-        let variants: Vec<PlanetType> = PlanetType::iter().collect();
-        *variants.choose(&mut rng).unwrap()
+        *PlanetType::all().choose(&mut rng).unwrap()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BlackAdidasShoe => "BlackAdidasShoe",
+            Ciuc => "Ciuc",
+            HoustonWeHaveABorrow => "HoustonWeHaveABorrow",
+            ImmutableCosmicBorrow => "ImmutableCosmicBorrow",
+            OneMillionCrabs => "OneMillionCrabs",
+            Rustrelli => "Rustrelli",
+            RustyCrab => "RustyCrab",
+            PlanetType::Custom(name) => *name,
+        }
+    }
+
+    /// Converts the numeric type id of the CSV init file, unknown ids get a random type
+    pub fn from_type_id(type_id: u32) -> Self {
+        match type_id {
+            0 => BlackAdidasShoe,
+            1 => Ciuc,
+            2 => HoustonWeHaveABorrow,
+            3 => ImmutableCosmicBorrow,
+            4 => OneMillionCrabs,
+            5 => Rustrelli,
+            6 => RustyCrab,
+            _ => PlanetType::random(),
+        }
+    }
+
+    /// Numeric type id used by the CSV init file, the inverse of `from_type_id`.
+    /// Custom planets have no id, the CSV file refers to them by name
+    pub fn type_id(&self) -> Option<u32> {
+        Self::BUILTIN
+            .iter()
+            .position(|typ| typ == self)
+            .map(|position| position as u32)
+    }
+
+    /// None for a custom type that was never registered
+    pub fn class(&self) -> Option<PlanetClass> {
+        self.capabilities().map(|capabilities| capabilities.class)
+    }
+
+    /// Metadata of the registry entry of this type, none for a custom type that was never registered
+    pub fn capabilities(&self) -> Option<PlanetCapabilities> {
+        self.entry().ok().map(|entry| entry.capabilities.clone())
+    }
+
+    /// Every planet type of the given class
+    pub fn of_class(class: PlanetClass) -> Vec<Self> {
+        PlanetType::all()
+            .into_iter()
+            .filter(|typ| typ.class() == Some(class))
+            .collect()
+    }
+
+    //`PlanetType::Custom` can be built by hand, so a type may have no entry
+    fn entry(&self) -> Result<Arc<PlanetEntry>, String> {
+        PLANET_REGISTRY
+            .read()
+            .unwrap()
+            .get(self)
+            .cloned()
+            .ok_or_else(|| format!("the planet type {self} is not registered"))
+    }
+
+    /// Creates a planet of this type with the factory of its registry entry
+    pub fn create_planet(
+        &self,
+        rx_orchestrator: Receiver<OrchestratorToPlanet>,
+        tx_orchestrator: Sender<PlanetToOrchestrator>,
+        rx_explorer: Receiver<ExplorerToPlanet>,
+        planet_id: u32,
+        config: &PlanetConfig,
+    ) -> Result<Planet, String> {
+        //The lock is released before calling the factory
+        let entry = self.entry()?;
+        (entry.factory)(rx_orchestrator, tx_orchestrator, rx_explorer, planet_id, config)
     }
+
+    /// Finds a planet type by name, custom planets included
+    ///
+    /// The name is case insensitive and can be written in snake case,
+    /// e.g. "OneMillionCrabs", "one_million_crabs" and "onemillioncrabs" are the same type.
+    /// "random" chooses one of the types at random. The mock planet is found only by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized = normalize_name(name);
+        if normalized == "random" {
+            return Some(PlanetType::random());
+        }
+        PlanetType::registered()
+            .into_iter()
+            .find(|typ| normalize_name(typ.name()) == normalized)
+    }
+
+    /// Checks the config of a planet of this type without creating it
+    pub fn check_config(&self, config: &PlanetConfig) -> Result<(), String> {
+        self.entry()?;
+        #[cfg(any(test, feature = "mock-planets"))]
+        if *self == PlanetType::MOCK {
+            return super::mock_planets::MockBehaviour::from_config(config).map(|_| ());
+        }
+        match self {
+            HoustonWeHaveABorrow => {
+                let config: HoustonConfig = parse_config(config)?;
+                rocket_strategy_by_name(&config.rocket_strategy)?;
+                basic_resource_by_name(&config.basic_resource)?;
+                Ok(())
+            }
+            ImmutableCosmicBorrow => {
+                let config: ImmutableCosmicBorrowConfig = parse_config(config)?;
+                config.check()
+            }
+            Rustrelli => {
+                let config: RustrelliConfig = parse_config(config)?;
+                request_limit_by_name(&config.request_limit).map(|_| ())
+            }
+            //Custom factories check their own config when they are called
+            PlanetType::Custom(_) => Ok(()),
+            _ => no_config(config),
+        }
+    }
+}
+
+impl fmt::Display for PlanetType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+//Planet types travel by name, so that custom planets can be sent to and from the UI too
+impl Serialize for PlanetType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for PlanetType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if normalize_name(&name) == "random" {
+            return Err(serde::de::Error::custom("a random planet type cannot be deserialized"));
+        }
+        PlanetType::from_name(&name).ok_or_else(|| serde::de::Error::custom(format!("unknown planet type '{name}'")))
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Adds a planet implementation of another crate to the registry
+///
+/// Galaxy files and UI commands can then use it by name like the builtin planets,
+/// e.g. `register_planet("my_planet", PlanetCapabilities::of_class(PlanetClass::B), factory)`.
+/// Fails if the name is already used by another planet type
+pub fn register_planet(
+    name: &str,
+    capabilities: PlanetCapabilities,
+    factory: PlanetFactory,
+) -> Result<PlanetType, String> {
+    let normalized = normalize_name(name);
+    if normalized.is_empty() || normalized == "random" {
+        return Err(format!("'{name}' cannot be used as a planet name"));
+    }
+    let mut registry = PLANET_REGISTRY.write().unwrap();
+    if let Some(existing) = registry.keys().find(|typ| normalize_name(typ.name()) == normalized) {
+        return Err(format!("The name '{name}' is already used by the planet type {existing}"));
+    }
+    //Registered types live as long as the program, like the builtin ones
+    let planet_type = PlanetType::Custom(Box::leak(name.to_string().into_boxed_str()));
+    registry.insert(planet_type, Arc::new(PlanetEntry { capabilities, factory }));
+    Ok(planet_type)
+}
+
+/// Config of HoustonWeHaveABorrow, e.g. `config = { basic_resource = "oxygen" }`
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct HoustonConfig {
+    rocket_strategy: String,
+    /// "none" creates the planet without a basic resource
+    basic_resource: String,
+}
+
+impl Default for HoustonConfig {
+    fn default() -> Self {
+        Self {
+            rocket_strategy: "default".to_string(),
+            basic_resource: "hydrogen".to_string(),
+        }
+    }
+}
+
+/// Config of Rustrelli, e.g. `config = { request_limit = "none" }`
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RustrelliConfig {
+    request_limit: String,
+}
+
+impl Default for RustrelliConfig {
+    fn default() -> Self {
+        Self {
+            request_limit: "none".to_string(),
+        }
+    }
+}
+
+/// Config of ImmutableCosmicBorrow, the parameters of its AI,
+/// e.g. `config = { sunray_threshold = 0.5, asteroid_threshold = 0.2 }`
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ImmutableCosmicBorrowConfig {
+    /// Whether the planet runs its AI at all
+    ai_enabled: bool,
+    /// Energy the planet keeps before it starts answering the explorers
+    energy_reserve: f64,
+    /// Whether the AI builds a rocket as soon as a cell is charged
+    rocket_first: bool,
+    /// Charge ratio above which the AI spends energy on the explorers
+    sunray_threshold: f64,
+    /// Charge ratio below which the AI keeps the energy for a rocket
+    asteroid_threshold: f64,
+    /// How far back the AI looks at the sunrays it received, in seconds
+    sunray_window_secs: u64,
+    /// How far back the AI looks at the asteroids it received, in seconds
+    asteroid_window_secs: u64,
+}
+
+impl Default for ImmutableCosmicBorrowConfig {
+    fn default() -> Self {
+        Self {
+            ai_enabled: true,
+            energy_reserve: 2.0,
+            rocket_first: true,
+            sunray_threshold: 0.0,
+            asteroid_threshold: 0.0,
+            sunray_window_secs: 10,
+            asteroid_window_secs: 10,
+        }
+    }
+}
+
+impl ImmutableCosmicBorrowConfig {
+    fn check(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("energy_reserve", self.energy_reserve),
+            ("sunray_threshold", self.sunray_threshold),
+            ("asteroid_threshold", self.asteroid_threshold),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{name} must be a number not below 0, got {value}"));
+            }
+        }
+        for (name, value) in [
+            ("sunray_threshold", self.sunray_threshold),
+            ("asteroid_threshold", self.asteroid_threshold),
+        ] {
+            if value > 1.0 {
+                return Err(format!("{name} is a ratio between 0 and 1, got {value}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn ai(&self) -> immutable_cosmic_borrow::Ai {
+        immutable_cosmic_borrow::Ai::new(
+            self.rocket_first,
+            self.sunray_threshold,
+            self.asteroid_threshold,
+            Duration::from_secs(self.sunray_window_secs),
+            Duration::from_secs(self.asteroid_window_secs),
+        )
+    }
+}
+
+/// Reads the config of a planet, a missing config gives the default parameters
+fn parse_config<T: DeserializeOwned + Default>(config: &PlanetConfig) -> Result<T, String> {
+    if config.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(config.clone()).map_err(|e| format!("invalid planet config: {e}"))
+}
+
+/// For the planets whose factory has no parameters
+fn no_config(config: &PlanetConfig) -> Result<(), String> {
+    match config.is_null() {
+        true => Ok(()),
+        false => Err("this planet type does not accept a config".to_string()),
+    }
+}
+
+fn basic_resource_by_name(name: &str) -> Result<Option<BasicResourceType>, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "none" => Ok(None),
+        "oxygen" => Ok(Some(BasicResourceType::Oxygen)),
+        "hydrogen" => Ok(Some(BasicResourceType::Hydrogen)),
+        "carbon" => Ok(Some(BasicResourceType::Carbon)),
+        "silicon" => Ok(Some(BasicResourceType::Silicon)),
+        _ => Err(format!(
+            "unknown basic resource '{name}', expected one of: none, oxygen, hydrogen, carbon, silicon"
+        )),
+    }
+}
+
+fn rocket_strategy_by_name(name: &str) -> Result<houston_we_have_a_borrow::RocketStrategy, String> {
+    match normalize_name(name).as_str() {
+        "default" => Ok(houston_we_have_a_borrow::RocketStrategy::Default),
+        "safe" => Ok(houston_we_have_a_borrow::RocketStrategy::Safe),
+        "emergencyreserve" => Ok(houston_we_have_a_borrow::RocketStrategy::EmergencyReserve),
+        "disabled" => Ok(houston_we_have_a_borrow::RocketStrategy::Disabled),
+        _ => Err(format!(
+            "unknown rocket strategy '{name}', expected one of: default, safe, emergency_reserve, disabled"
+        )),
+    }
+}
+
+fn request_limit_by_name(name: &str) -> Result<rustrelli::ExplorerRequestLimit, String> {
+    match normalize_name(name).as_str() {
+        "none" => Ok(rustrelli::ExplorerRequestLimit::None),
+        "fairshare" => Ok(rustrelli::ExplorerRequestLimit::FairShare),
+        _ => Err(format!(
+            "unknown explorer request limit '{name}', expected one of: none, fair_share"
+        )),
+    }
+}
+
+//Class C planets combine every recipe
+fn all_complex_resources() -> Vec<ComplexResourceType> {
+    vec![
+        ComplexResourceType::Water,
+        ComplexResourceType::Diamond,
+        ComplexResourceType::Life,
+        ComplexResourceType::Robot,
+        ComplexResourceType::Dolphin,
+        ComplexResourceType::AIPartner,
+    ]
 }
 
-pub static PLANET_REGISTRY: Lazy<HashMap<PlanetType, PlanetFactory>> = Lazy::new(|| {
-    HashMap::from([
+/// Every planet type that can be created, custom planets are added with `register_planet`
+pub static PLANET_REGISTRY: Lazy<RwLock<HashMap<PlanetType, Arc<PlanetEntry>>>> = Lazy::new(|| {
+    #[cfg_attr(not(any(test, feature = "mock-planets")), allow(unused_mut))]
+    let mut registry = HashMap::from([
         //black_adidas_shoe: Type D
         (
             BlackAdidasShoe,
-            Box::new(|rx_o, tx_o, rx_e, planet_id| {
-                black_adidas_shoe::planet::create_planet(rx_o, tx_o, rx_e, planet_id)
-            }) as PlanetFactory,
+            Arc::new(PlanetEntry {
+                capabilities: PlanetCapabilities::of_class(PlanetClass::D)
+                    .with_energy_cell_count(5)
+                    .with_basic_resources(vec![
+                        BasicResourceType::Oxygen,
+                        BasicResourceType::Hydrogen,
+                        BasicResourceType::Carbon,
+                        BasicResourceType::Silicon,
+                    ])
+                    .with_complex_resources(vec![]),
+                factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+                    no_config(config)?;
+                    black_adidas_shoe::planet::create_planet(rx_o, tx_o, rx_e, planet_id)
+                }) as PlanetFactory,
+            }),
         ),
         //ciuc: Type A
         (
             Ciuc,
-            Box::new(|rx_o, tx_p, rx_e, id| Ok(ciuc_planet::create_planet(rx_o, tx_p, rx_e, id)))
-                as PlanetFactory,
+            Arc::new(PlanetEntry {
+                capabilities: PlanetCapabilities::of_class(PlanetClass::A)
+                    .with_energy_cell_count(5)
+                    .with_basic_resources(vec![BasicResourceType::Carbon])
+                    .with_complex_resources(vec![]),
+                factory: Box::new(|rx_o, tx_p, rx_e, id, config: &PlanetConfig| {
+                    no_config(config)?;
+                    Ok(ciuc_planet::create_planet(rx_o, tx_p, rx_e, id))
+                }) as PlanetFactory,
+            }),
         ),
         //houston_we_have_a_borrow: Type C
         (
             HoustonWeHaveABorrow,
-            Box::new(|rx_o, tx_o, rx_e, planet_id| {
-                let rocket = houston_we_have_a_borrow::RocketStrategy::Default;
-                let basic_resource = BasicResourceType::Hydrogen;
-                houston_we_have_a_borrow::houston_we_have_a_borrow(
-                    rx_o,
-                    tx_o,
-                    rx_e,
-                    planet_id,
-                    rocket,
-                    Some(basic_resource),
-                )
-            }) as PlanetFactory,
+            Arc::new(PlanetEntry {
+                //One basic resource at a time, chosen by the config
+                capabilities: PlanetCapabilities::of_class(PlanetClass::C)
+                    .with_basic_resources(vec![
+                        BasicResourceType::Oxygen,
+                        BasicResourceType::Hydrogen,
+                        BasicResourceType::Carbon,
+                        BasicResourceType::Silicon,
+                    ])
+                    .with_complex_resources(all_complex_resources()),
+                factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+                    let config: HoustonConfig = parse_config(config)?;
+                    let rocket = rocket_strategy_by_name(&config.rocket_strategy)?;
+                    let basic_resource = basic_resource_by_name(&config.basic_resource)?;
+                    houston_we_have_a_borrow::houston_we_have_a_borrow(
+                        rx_o,
+                        tx_o,
+                        rx_e,
+                        planet_id,
+                        rocket,
+                        basic_resource,
+                    )
+                }) as PlanetFactory,
+            }),
         ),
         //immutable_cosmic_borrow: Type C
         (
             ImmutableCosmicBorrow,
-            Box::new(|rx_o, tx_o, rx_e, planet_id| {
-                one_million_crabs::planet::create_planet(rx_o, tx_o, rx_e, planet_id)
-            }) as PlanetFactory,
-            // Box::new(|rx_o, tx_p, rx_e, id| {
-            //     let duration = Duration::from_secs(10);
-            //     let ai = immutable_cosmic_borrow::Ai::new(
-            //         true,
-            //         0.0,
-            //         0.0,
-            //         duration.clone(),
-            //         duration.clone(),
-            //     );
-                
-            //     immutable_cosmic_borrow::create_planet(true, 2.0, ai, id, (rx_o, tx_p), rx_e)
-            // }) as PlanetFactory,
+            Arc::new(PlanetEntry {
+                capabilities: PlanetCapabilities::of_class(PlanetClass::C)
+                    .with_basic_resources(vec![BasicResourceType::Silicon])
+                    .with_complex_resources(all_complex_resources()),
+                factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+                    let config: ImmutableCosmicBorrowConfig = parse_config(config)?;
+                    config.check()?;
+                    immutable_cosmic_borrow::create_planet(
+                        config.ai_enabled,
+                        config.energy_reserve,
+                        config.ai(),
+                        planet_id,
+                        (rx_o, tx_o),
+                        rx_e,
+                    )
+                }) as PlanetFactory,
+            }),
         ),
         // one_million_crabs: Type D
         (
             OneMillionCrabs,
-            Box::new(|rx_o, tx_o, rx_e, planet_id| {
-                one_million_crabs::planet::create_planet(rx_o, tx_o, rx_e, planet_id)
-            }) as PlanetFactory,
+            Arc::new(PlanetEntry {
+                capabilities: PlanetCapabilities::of_class(PlanetClass::D)
+                    .with_energy_cell_count(5)
+                    .with_basic_resources(vec![
+                        BasicResourceType::Oxygen,
+                        BasicResourceType::Hydrogen,
+                        BasicResourceType::Carbon,
+                        BasicResourceType::Silicon,
+                    ])
+                    .with_complex_resources(vec![]),
+                factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+                    no_config(config)?;
+                    one_million_crabs::planet::create_planet(rx_o, tx_o, rx_e, planet_id)
+                }) as PlanetFactory,
+            }),
         ),
         // TODO manca planet_id
         (
             Rustrelli,
-            Box::new(|rx_o, tx_o, rx_e, planet_id| {
-                let request_limit=rustrelli::ExplorerRequestLimit::None;
-                Ok(rustrelli::create_planet(planet_id, rx_o, tx_o, rx_e, request_limit))
-            }) as PlanetFactory,
+            Arc::new(PlanetEntry {
+                capabilities: PlanetCapabilities::of_class(PlanetClass::C)
+                    .with_basic_resources(vec![BasicResourceType::Oxygen])
+                    .with_complex_resources(all_complex_resources()),
+                factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+                    let config: RustrelliConfig = parse_config(config)?;
+                    let request_limit = request_limit_by_name(&config.request_limit)?;
+                    Ok(rustrelli::create_planet(planet_id, rx_o, tx_o, rx_e, request_limit))
+                }) as PlanetFactory,
+            }),
         ),
         // TODO manca planet_id
         (
             RustyCrab,
-            Box::new(|rx_o, tx_o, rx_e, planet_id| {
-                Ok(rusty_crab::planet::create_planet(rx_o, tx_o, rx_e, planet_id))
-            }) as PlanetFactory,
+            Arc::new(PlanetEntry {
+                capabilities: PlanetCapabilities::of_class(PlanetClass::C)
+                    .with_basic_resources(vec![BasicResourceType::Hydrogen])
+                    .with_complex_resources(all_complex_resources()),
+                factory: Box::new(|rx_o, tx_o, rx_e, planet_id, config: &PlanetConfig| {
+                    no_config(config)?;
+                    Ok(rusty_crab::planet::create_planet(rx_o, tx_o, rx_e, planet_id))
+                }) as PlanetFactory,
+            }),
         ),
-    ])
+    ]);
+    #[cfg(any(test, feature = "mock-planets"))]
+    registry.insert(PlanetType::MOCK, Arc::new(super::mock_planets::mock_entry()));
+    RwLock::new(registry)
 });
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use common_game::components::resource::{BasicResourceType, ComplexResourceType, ResourceType};
+use serde::{Deserialize, Serialize};
+
+use crate::components::explorer::BagType;
+use crate::messages::json::{resource_type, resource_types};
+use crate::utils::registry::PlanetType;
+use crate::utils::Status;
+
+/// When the game is over, see `settings::set_end_conditions`
+///
+/// The game ends as soon as one of the configured conditions is met
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EndCondition {
+    AllPlanetsDead,
+    /// Only once at least one explorer has been spawned
+    AllExplorersDead,
+    /// The game ends at the end of the tick
+    TickLimit { tick: u64 },
+    /// Some explorer has the resource in its bag
+    ResourceCrafted {
+        #[serde(with = "resource_type")]
+        resource: ResourceType,
+    },
+}
+
+/// The end condition that was met
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GameOverReason {
+    AllPlanetsDead,
+    AllExplorersDead,
+    TickLimit { tick: u64 },
+    ResourceCrafted {
+        explorer_id: u32,
+        #[serde(with = "resource_type")]
+        resource: ResourceType,
+    },
+}
+
+/// Final results of a game, sent to the UI with `GameToUi::GameOver`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scoreboard {
+    pub tick: u64,
+    pub reason: GameOverReason,
+    pub planets: BTreeMap<u32, PlanetScore>,
+    pub explorers: BTreeMap<u32, ExplorerScore>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetScore {
+    pub planet_type: Option<PlanetType>,
+    pub status: Status,
+    /// Ticks from the creation of the planet to its death, or to the end of the game
+    pub survived_ticks: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerScore {
+    pub status: Status,
+    /// Content of the bag at the end of the game
+    #[serde(with = "resource_types")]
+    pub resources: BagType,
+    /// Sum of the `resource_weight` of the resources
+    pub score: u32,
+}
+
+impl Scoreboard {
+    /// Explorer with the highest score, the lowest id wins a tie
+    pub fn winner(&self) -> Option<u32> {
+        self.explorers
+            .iter()
+            .max_by(|(id_one, one), (id_two, two)| one.score.cmp(&two.score).then(id_two.cmp(id_one)))
+            .map(|(explorer_id, _)| *explorer_id)
+    }
+}
+
+impl ExplorerScore {
+    pub fn new(status: Status, resources: BagType) -> Self {
+        let score = resources.iter().map(resource_weight).sum();
+        Self { status, resources, score }
+    }
+}
+
+/// Value of a resource in the score: basic resources are worth 1,
+/// complex ones one more than the deepest resource of their recipe
+pub fn resource_weight(resource: &ResourceType) -> u32 {
+    match resource {
+        ResourceType::Basic(
+            BasicResourceType::Hydrogen | BasicResourceType::Oxygen | BasicResourceType::Carbon | BasicResourceType::Silicon,
+        ) => 1,
+        ResourceType::Complex(ComplexResourceType::Diamond | ComplexResourceType::Water) => 2,
+        ResourceType::Complex(ComplexResourceType::Life) => 3,
+        ResourceType::Complex(ComplexResourceType::Robot | ComplexResourceType::Dolphin) => 4,
+        ResourceType::Complex(ComplexResourceType::AIPartner) => 5,
+    }
+}
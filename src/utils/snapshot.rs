@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::registry::PlanetType;
+use crate::utils::Status;
+use crate::utils::topology_analysis::TopologyAnalysis;
+
+/// Owned copy of the galaxy state handed to the UI
+///
+/// Unlike the shared `Arc<RwLock<..>>` used internally by the orchestrator,
+/// a snapshot holds no locks: the UI can keep it as long as it wants
+/// without stalling the game loop.
+///
+/// `version` is increased by the orchestrator every time something inside
+/// the galaxy changes, so two snapshots with the same version describe the
+/// same state and the UI can skip redrawing the frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalaxySnapshot {
+    pub version: u64,
+    pub tick: u64,
+    pub topology: TopologySnapshot,
+    /// Critical planets, components and diameter of the live planets
+    #[serde(default)]
+    pub analysis: TopologyAnalysis,
+    pub planets: BTreeMap<u32, PlanetSnapshot>,
+    pub explorers: BTreeMap<u32, ExplorerSnapshot>,
+}
+
+/// Adjacency matrix of the galaxy
+///
+/// `planet_ids[i]` is the id of the planet represented by the i-th row
+/// (and column) of `adjacency`, ids are in ascending order.
+/// The orchestrator keeps the links in a sparse `GalaxyGraph`, the matrix
+/// is only built for the UI.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TopologySnapshot {
+    pub planet_ids: Vec<u32>,
+    pub adjacency: Vec<Vec<bool>>,
+    /// Links that existed and have been removed during the game, as (lower id, higher id)
+    #[serde(default)]
+    pub destroyed_links: Vec<(u32, u32)>,
+}
+
+impl TopologySnapshot {
+    pub fn are_linked(&self, planet_one: u32, planet_two: u32) -> bool {
+        self.neighbors(planet_one).contains(&planet_two)
+    }
+
+    /// Returns the ids of the planets directly connected to `planet_id`
+    pub fn neighbors(&self, planet_id: u32) -> Vec<u32> {
+        let Some(row) = self.planet_ids.iter().position(|id| *id == planet_id) else {
+            return Vec::new();
+        };
+        self.adjacency[row]
+            .iter()
+            .enumerate()
+            .filter(|(_, connected)| **connected)
+            .map(|(col, _)| self.planet_ids[col])
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanetSnapshot {
+    pub status: Status,
+    pub planet_type: Option<PlanetType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorerSnapshot {
+    pub status: Status,
+    pub planet_id: u32,
+}
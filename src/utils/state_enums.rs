@@ -1,8 +1,21 @@
-#[derive(PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub enum Status {
     Running,
     Paused,
+    //Did not answer to a request of the orchestrator in time, it is back to Running if it answers later
+    Unresponsive,
     Dead,
+    //The thread of the planet panicked or ended without being killed
+    Crashed,
+}
+
+impl Status {
+    /// Dead and crashed actors are no longer part of the game
+    pub fn is_dead(&self) -> bool {
+        matches!(self, Status::Dead | Status::Crashed)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
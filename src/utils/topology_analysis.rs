@@ -0,0 +1,211 @@
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+/// Links of the live planets, by planet id
+pub type PlanetGraph = BTreeMap<u32, BTreeSet<u32>>;
+
+/// Analysis shared by the orchestrator with the explorers, updated on every topology change
+pub type SharedTopologyAnalysis = Arc<RwLock<TopologyAnalysis>>;
+
+/// Structure of the graph of the live planets
+///
+/// The critical nodes of the galaxy are the `articulation_points`:
+/// destroying one of them splits the planets around it in separate components.
+/// In the same way removing one of the `bridges` splits the galaxy.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TopologyAnalysis {
+    pub graph: PlanetGraph,
+    /// Groups of planets connected to each other, the biggest first
+    pub components: Vec<Vec<u32>>,
+    pub articulation_points: Vec<u32>,
+    /// Links whose removal splits a component, as (lower id, higher id)
+    pub bridges: Vec<(u32, u32)>,
+    /// Longest shortest path between two connected planets, in number of links
+    pub diameter: u32,
+}
+
+impl TopologyAnalysis {
+    pub fn compute(graph: PlanetGraph) -> Self {
+        let mut components = components(&graph);
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+
+        let (articulation_points, bridges) = critical_elements(&graph);
+
+        let diameter = graph
+            .keys()
+            .flat_map(|start| distances(&graph, *start).into_values())
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            graph,
+            components,
+            articulation_points,
+            bridges,
+            diameter,
+        }
+    }
+
+    /// Builds the graph of the live planets from the adjacency matrix, `planet_ids[i]` is the
+    /// planet of the i-th row
+    pub fn from_adjacency(planet_ids: &[u32], adjacency: &[Vec<bool>], is_alive: impl Fn(u32) -> bool) -> Self {
+        let mut graph = PlanetGraph::new();
+        for (row, connections) in adjacency.iter().enumerate() {
+            let id = planet_ids[row];
+            if !is_alive(id) {
+                continue;
+            }
+            let neighbors = graph.entry(id).or_default();
+            for (col, connected) in connections.iter().enumerate() {
+                if *connected && is_alive(planet_ids[col]) && col != row {
+                    neighbors.insert(planet_ids[col]);
+                }
+            }
+        }
+        Self::compute(graph)
+    }
+
+    pub fn is_articulation_point(&self, planet_id: u32) -> bool {
+        self.articulation_points.contains(&planet_id)
+    }
+
+    /// True if both planets are alive and there is a path between them
+    pub fn are_connected(&self, from: u32, to: u32) -> bool {
+        self.components.iter().any(|c| c.contains(&from) && c.contains(&to))
+    }
+
+    /// Planets crossed going from `from` to `to` with the fewest jumps, both included
+    pub fn shortest_path(&self, from: u32, to: u32) -> Option<Vec<u32>> {
+        if !self.graph.contains_key(&from) || !self.graph.contains_key(&to) {
+            return None;
+        }
+        let mut previous: BTreeMap<u32, u32> = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+        previous.insert(from, from);
+        while let Some(id) = queue.pop_front() {
+            if id == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = previous[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.graph.get(&id).into_iter().flatten() {
+                if let Entry::Vacant(entry) = previous.entry(*next) {
+                    entry.insert(id);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Number of jumps between two planets, if they are connected
+    pub fn distance(&self, from: u32, to: u32) -> Option<u32> {
+        self.shortest_path(from, to).map(|path| path.len() as u32 - 1)
+    }
+}
+
+fn distances(graph: &PlanetGraph, start: u32) -> BTreeMap<u32, u32> {
+    let mut distances = BTreeMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(id) = queue.pop_front() {
+        let distance = distances[&id];
+        for next in graph.get(&id).into_iter().flatten() {
+            if let Entry::Vacant(entry) = distances.entry(*next) {
+                entry.insert(distance + 1);
+                queue.push_back(*next);
+            }
+        }
+    }
+    distances
+}
+
+fn components(graph: &PlanetGraph) -> Vec<Vec<u32>> {
+    let mut visited = BTreeSet::new();
+    let mut components = Vec::new();
+    for start in graph.keys() {
+        if visited.contains(start) {
+            continue;
+        }
+        let component: Vec<u32> = distances(graph, *start).into_keys().collect();
+        visited.extend(component.iter().copied());
+        components.push(component);
+    }
+    components
+}
+
+//Tarjan's algorithm, the galaxies are small so the recursion depth is not a problem
+struct Tarjan<'a> {
+    graph: &'a PlanetGraph,
+    timer: u32,
+    discovery: BTreeMap<u32, u32>,
+    low: BTreeMap<u32, u32>,
+    articulation_points: BTreeSet<u32>,
+    bridges: Vec<(u32, u32)>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, id: u32, parent: Option<u32>) {
+        //Copied out of self, so that the neighbors can be borrowed during the recursion
+        let graph = self.graph;
+        self.timer += 1;
+        self.discovery.insert(id, self.timer);
+        self.low.insert(id, self.timer);
+        let mut children = 0;
+
+        for &next in graph.get(&id).into_iter().flatten() {
+            if Some(next) == parent {
+                continue;
+            }
+            match self.discovery.get(&next) {
+                Some(&next_discovery) => {
+                    let low = self.low[&id].min(next_discovery);
+                    self.low.insert(id, low);
+                }
+                None => {
+                    children += 1;
+                    self.visit(next, Some(id));
+                    let low = self.low[&id].min(self.low[&next]);
+                    self.low.insert(id, low);
+
+                    if parent.is_some() && self.low[&next] >= self.discovery[&id] {
+                        self.articulation_points.insert(id);
+                    }
+                    if self.low[&next] > self.discovery[&id] {
+                        self.bridges.push((id.min(next), id.max(next)));
+                    }
+                }
+            }
+        }
+
+        if parent.is_none() && children > 1 {
+            self.articulation_points.insert(id);
+        }
+    }
+}
+
+fn critical_elements(graph: &PlanetGraph) -> (Vec<u32>, Vec<(u32, u32)>) {
+    let mut tarjan = Tarjan {
+        graph,
+        timer: 0,
+        discovery: BTreeMap::new(),
+        low: BTreeMap::new(),
+        articulation_points: BTreeSet::new(),
+        bridges: Vec::new(),
+    };
+    for id in graph.keys() {
+        if !tarjan.discovery.contains_key(id) {
+            tarjan.visit(*id, None);
+        }
+    }
+    let mut bridges = tarjan.bridges;
+    bridges.sort();
+    (tarjan.articulation_points.into_iter().collect(), bridges)
+}
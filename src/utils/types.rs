@@ -7,6 +7,10 @@ use common_game::protocols::planet_explorer::ExplorerToPlanet;
 use crossbeam_channel::{Receiver, Sender};
 
 use crate::utils::Status;
+use crate::utils::galaxy_graph::GalaxyGraph;
+
+/// Parameters of a single planet, the `config` of the galaxy file. `Null` means default parameters
+pub type PlanetConfig = serde_json::Value;
 
 pub type PlanetFactory = Box<
     dyn Fn(
@@ -14,13 +18,14 @@ pub type PlanetFactory = Box<
             Sender<PlanetToOrchestrator>,
             Receiver<ExplorerToPlanet>,
             u32,
+            &PlanetConfig,
         ) -> Result<Planet, String>
         + Send
         + Sync,
 >;
 
 
-pub type GalaxyTopology = Arc<RwLock<Vec<Vec<bool>>>>;
+pub type GalaxyTopology = Arc<RwLock<GalaxyGraph>>;
 pub type PlanetStatus = Arc<RwLock<BTreeMap<u32, Status>>>;
 pub type ExplorerStatus = Arc<RwLock<BTreeMap<u32, Status>>>;
 